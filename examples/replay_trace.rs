@@ -0,0 +1,42 @@
+// Replays a trace recorded with the "Record watcher trace" setting and prints the timer
+// events it produces.
+//
+// cargo run --example replay_trace --target <host triple> -- <trace file>
+
+use mcc_splitter_asr::timer::{RecordingTimer, TimerCommand};
+
+fn main() {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: replay_trace <trace file>");
+        std::process::exit(1);
+    };
+    let trace = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("failed to read {}: {}", path, e);
+        std::process::exit(1);
+    });
+
+    let mut timer = RecordingTimer::default();
+    if let Err(e) = mcc_splitter_asr::replay_trace(&trace, &mut timer) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+
+    let mut game_time_paused = false;
+    for event in &timer.events {
+        match &event.command {
+            TimerCommand::Start | TimerCommand::Split | TimerCommand::Reset => {
+                let game_time = timer.game_time_at(event.tick).unwrap_or_default();
+                println!("{:>8} {:?} (game time {:.3}s)", event.tick, event.command, game_time.as_seconds_f64());
+            }
+            TimerCommand::PauseGameTime if !game_time_paused => {
+                game_time_paused = true;
+                println!("{:>8} PauseGameTime", event.tick);
+            }
+            TimerCommand::ResumeGameTime if game_time_paused => {
+                game_time_paused = false;
+                println!("{:>8} ResumeGameTime", event.tick);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use asr::time::Duration;
+
+// IGT jumps larger than this between two reads are ignored, e.g. when a revert or a
+// stale read makes the value jump.
+const MAX_STEP: u32 = 300;
+
+// In-game time of one game in a run, counted in game ticks. A level's time is banked when
+// it ends, rounded to what the game shows on screen: down to the second on a PGCR or load
+// and to the nearest second on a restart.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IgtAccumulator {
+    tickrate: u32,
+    // Ticks banked from completed levels and restarts
+    completed: u32,
+    // Ticks in the current level, None until the level's IGT has been seen
+    level: Option<u32>,
+    // IGT the last level ended on. The next level only starts counting once IGT drops
+    // below it or to 0, so a value left over from the last level isn't counted twice.
+    ended_at: Option<u32>,
+    // The PGCR banked the level, so the load after it doesn't
+    pgcr_shown: bool,
+
+    pub levels_completed: u32,
+    pub restarts: u32,
+}
+
+impl IgtAccumulator {
+    pub fn new(tickrate: u32) -> Self {
+        Self {
+            tickrate,
+            ..Default::default()
+        }
+    }
+
+    pub fn tickrate(&self) -> u32 {
+        self.tickrate
+    }
+
+    // Ticks in the current level so far.
+    pub fn level_ticks(&self) -> u32 {
+        self.level.unwrap_or_default()
+    }
+
+    pub fn level_time(&self) -> Duration {
+        ticks_to_duration(self.level_ticks(), self.tickrate)
+    }
+
+    // Total ticks for the run, leaving out the current level while loading.
+    pub fn ticks(&self, loading: bool) -> u32 {
+        if loading {
+            self.completed
+        } else {
+            self.completed + self.level_ticks()
+        }
+    }
+
+    pub fn time(&self, loading: bool) -> Duration {
+        ticks_to_duration(self.ticks(loading), self.tickrate)
+    }
+
+    // Follows IGT for one tick.
+    pub fn update(&mut self, igt: u32, igt_old: u32, loading: bool) {
+        match self.level {
+            Some(ticks) => {
+                if igt > igt_old && igt - igt_old < MAX_STEP {
+                    self.level = Some(ticks + (igt - igt_old));
+                }
+            }
+            None => {
+                if !loading && !self.pgcr_shown && self.ended_at.is_none_or(|ended_at| igt < ended_at || igt == 0) {
+                    self.level = Some(igt);
+                    self.ended_at = None;
+                }
+            }
+        }
+    }
+
+    // Level finished with a PGCR. Returns true as the level's split.
+    pub fn pgcr(&mut self, igt: u32) -> bool {
+        self.finish_level(igt);
+        self.pgcr_shown = true;
+        true
+    }
+
+    // Loading screen started. Ends the level unless a PGCR already did, returning true if
+    // it did.
+    pub fn load(&mut self, igt: u32) -> bool {
+        let finished = !self.pgcr_shown;
+        if finished {
+            self.finish_level(igt);
+        }
+        self.pgcr_shown = false;
+        finished
+    }
+
+    // Back in the main menu, so whatever follows a PGCR isn't its load.
+    pub fn clear_pgcr(&mut self) {
+        self.pgcr_shown = false;
+    }
+
+    // Level restarted, IGT went back to `igt` from `igt_old`. With `exact` the level's ticks
+    // are kept as is, otherwise they're rounded to the nearest second and a restart within
+    // the first second still counts as one. Does nothing before the level started counting.
+    pub fn restart(&mut self, igt: u32, igt_old: u32, exact: bool) {
+        let Some(ticks) = self.level else { return };
+        self.completed += if exact {
+            ticks
+        } else if ticks < self.tickrate && igt_old < self.tickrate {
+            self.tickrate
+        } else {
+            round_nearest(ticks, self.tickrate)
+        };
+        self.level = Some(igt);
+        self.restarts += 1;
+    }
+
+    fn finish_level(&mut self, igt: u32) {
+        self.completed += round_down(self.level_ticks(), self.tickrate);
+        self.level = None;
+        self.ended_at = Some(igt);
+        self.levels_completed += 1;
+    }
+}
+
+fn round_down(ticks: u32, tickrate: u32) -> u32 {
+    if tickrate == 0 {
+        return ticks;
+    }
+    ticks - ticks % tickrate
+}
+
+fn round_nearest(ticks: u32, tickrate: u32) -> u32 {
+    if tickrate == 0 {
+        return ticks;
+    }
+    let rest = ticks % tickrate;
+    if rest > tickrate / 2 {
+        ticks + (tickrate - rest)
+    } else {
+        ticks - rest
+    }
+}
+
+fn ticks_to_duration(ticks: u32, tickrate: u32) -> Duration {
+    if tickrate == 0 {
+        return Duration::ZERO;
+    }
+    Duration::milliseconds(ticks as i64 * 1000 / tickrate as i64)
+}
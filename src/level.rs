@@ -0,0 +1,237 @@
+use crate::MCCGame;
+
+// Registry of the campaign levels of every game.
+//
+// Each game declares its levels in the order they're played, with the name MCC reports for
+// the loaded map and the mission name shown in game, and which missions a full game run
+// starts and ends on. Levels before the first mission are intro cutscenes, levels after the
+// last are outros. Names that don't belong to the campaign, like multiplayer and custom maps,
+// don't parse.
+macro_rules! levels {
+    ($level:ident, $game:ident, first: $first:ident, last: $last:ident, { $($variant:ident => $code:literal, $name:literal,)* }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum $level {
+            $($variant,)*
+        }
+
+        impl $level {
+            pub const ALL: &'static [$level] = &[$($level::$variant,)*];
+            pub const FIRST: $level = $level::$first;
+            pub const LAST: $level = $level::$last;
+            pub const MISSIONS: usize = $level::$last as usize - $level::$first as usize + 1;
+
+            // Parses the bytes of the level name watcher.
+            pub fn parse(code: &[u8]) -> Option<Self> {
+                Self::ALL.iter().copied().find(|level| level.code().as_bytes() == code)
+            }
+
+            pub fn code(self) -> &'static str {
+                match self {
+                    $($level::$variant => $code,)*
+                }
+            }
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $($level::$variant => $name,)*
+                }
+            }
+
+            // Position in the campaign counting from 1 at the first mission, None for
+            // cutscene levels.
+            pub fn number(self) -> Option<usize> {
+                self.is_mission().then(|| self as usize - Self::FIRST as usize + 1)
+            }
+
+            pub fn is_mission(self) -> bool {
+                (Self::FIRST as usize..=Self::LAST as usize).contains(&(self as usize))
+            }
+
+            pub fn is_intro(self) -> bool {
+                (self as usize) < Self::FIRST as usize
+            }
+
+            pub fn is_outro(self) -> bool {
+                (self as usize) > Self::LAST as usize
+            }
+
+            // The level played after this one.
+            pub fn next(self) -> Option<Self> {
+                Self::ALL.get(self as usize + 1).copied()
+            }
+        }
+
+        impl From<$level> for Level {
+            fn from(level: $level) -> Self {
+                Level::$game(level)
+            }
+        }
+    };
+}
+
+levels!(H1Level, Halo1, first: PillarOfAutumn, last: Maw, {
+    PillarOfAutumn => "a10", "The Pillar of Autumn",
+    Halo => "a30", "Halo",
+    TruthAndReconciliation => "a50", "The Truth and Reconciliation",
+    SilentCartographer => "b30", "The Silent Cartographer",
+    AssaultOnTheControlRoom => "b40", "Assault on the Control Room",
+    GuiltySpark => "c10", "343 Guilty Spark",
+    Library => "c20", "The Library",
+    TwoBetrayals => "c40", "Two Betrayals",
+    Keyes => "d20", "Keyes",
+    Maw => "d40", "The Maw",
+});
+
+levels!(H2Level, Halo2, first: Armory, last: GreatJourney, {
+    Heretic => "00a", "The Heretic",
+    Armory => "01a", "The Armory",
+    Cairo => "01b", "Cairo Station",
+    Outskirts => "03a", "Outskirts",
+    Metropolis => "03b", "Metropolis",
+    Arbiter => "04a", "The Arbiter",
+    Oracle => "04b", "The Oracle",
+    DeltaHalo => "05a", "Delta Halo",
+    Regret => "05b", "Regret",
+    SacredIcon => "06a", "Sacred Icon",
+    QuarantineZone => "06b", "Quarantine Zone",
+    Gravemind => "07a", "Gravemind",
+    Uprising => "08a", "Uprising",
+    HighCharity => "07b", "High Charity",
+    GreatJourney => "08b", "The Great Journey",
+});
+
+levels!(H3Level, Halo3, first: Sierra117, last: Halo, {
+    Arrival => "005", "Arrival",
+    Sierra117 => "010", "Sierra 117",
+    CrowsNest => "020", "Crow's Nest",
+    TsavoHighway => "030", "Tsavo Highway",
+    Storm => "040", "The Storm",
+    Floodgate => "050", "Floodgate",
+    Ark => "070", "The Ark",
+    Covenant => "100", "The Covenant",
+    Cortana => "110", "Cortana",
+    Halo => "120", "Halo",
+    Epilogue => "130", "Epilogue",
+});
+
+levels!(H4Level, Halo4, first: Dawn, last: Midnight, {
+    Dawn => "m10", "Dawn",
+    Requiem => "m02", "Requiem",
+    Forerunner => "m30", "Forerunner",
+    Infinity => "m40", "Infinity",
+    Reclaimer => "m60", "Reclaimer",
+    Shutdown => "m70", "Shutdown",
+    Composer => "m80", "Composer",
+    Midnight => "m90", "Midnight",
+});
+
+levels!(OdstLevel, Odst, first: MombasaStreets, last: CoastalHighway, {
+    PrepareToDrop => "c100", "Prepare to Drop",
+    MombasaStreets => "h100", "Mombasa Streets",
+    TayariPlaza => "sc10", "Tayari Plaza",
+    UpliftReserve => "sc11", "Uplift Reserve",
+    KizingoBoulevard => "sc13", "Kizingo Boulevard",
+    OniAlphaSite => "sc12", "ONI Alpha Site",
+    NmpdHq => "sc14", "NMPD HQ",
+    KikowaniStation => "sc15", "Kikowani Station",
+    DataHive => "l200", "Data Hive",
+    CoastalHighway => "l300", "Coastal Highway",
+    Epilogue => "c200", "Epilogue",
+});
+
+levels!(HrLevel, Reach, first: WinterContingency, last: PillarOfAutumn, {
+    NobleActual => "m05", "Noble Actual",
+    WinterContingency => "m10", "Winter Contingency",
+    OniSwordBase => "m20", "ONI: Sword Base",
+    Nightfall => "m30", "Nightfall",
+    TipOfTheSpear => "m35", "Tip of the Spear",
+    LongNightOfSolace => "m45", "Long Night of Solace",
+    Exodus => "m50", "Exodus",
+    NewAlexandria => "m52", "New Alexandria",
+    Package => "m60", "The Package",
+    PillarOfAutumn => "m70", "The Pillar of Autumn",
+});
+
+// Runs an expression on the game's level inside a Level.
+macro_rules! each_game {
+    ($value:expr, $level:ident => $body:expr) => {
+        match $value {
+            Level::Halo1($level) => $body,
+            Level::Halo2($level) => $body,
+            Level::Halo3($level) => $body,
+            Level::Halo4($level) => $body,
+            Level::Odst($level) => $body,
+            Level::Reach($level) => $body,
+        }
+    };
+}
+
+// A campaign level of any game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Halo1(H1Level),
+    Halo2(H2Level),
+    Halo3(H3Level),
+    Halo4(H4Level),
+    Odst(OdstLevel),
+    Reach(HrLevel),
+}
+
+impl Level {
+    // Every level of every game, a game at a time.
+    pub fn all() -> impl Iterator<Item = Level> {
+        let h1 = H1Level::ALL.iter().copied().map(Level::from);
+        let h2 = H2Level::ALL.iter().copied().map(Level::from);
+        let h3 = H3Level::ALL.iter().copied().map(Level::from);
+        let h4 = H4Level::ALL.iter().copied().map(Level::from);
+        let odst = OdstLevel::ALL.iter().copied().map(Level::from);
+        let hr = HrLevel::ALL.iter().copied().map(Level::from);
+        h1.chain(h2).chain(h3).chain(h4).chain(odst).chain(hr)
+    }
+
+    pub fn game(self) -> MCCGame {
+        match self {
+            Level::Halo1(_) => MCCGame::Halo1,
+            Level::Halo2(_) => MCCGame::Halo2,
+            Level::Halo3(_) => MCCGame::Halo3,
+            Level::Halo4(_) => MCCGame::Halo4,
+            Level::Odst(_) => MCCGame::ODST,
+            Level::Reach(_) => MCCGame::Reach,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        each_game!(self, level => level.code())
+    }
+
+    pub fn name(self) -> &'static str {
+        each_game!(self, level => level.name())
+    }
+
+    pub fn number(self) -> Option<usize> {
+        each_game!(self, level => level.number())
+    }
+
+    // Number of missions in the level's campaign.
+    pub fn missions(self) -> usize {
+        match self {
+            Level::Halo1(_) => H1Level::MISSIONS,
+            Level::Halo2(_) => H2Level::MISSIONS,
+            Level::Halo3(_) => H3Level::MISSIONS,
+            Level::Halo4(_) => H4Level::MISSIONS,
+            Level::Odst(_) => OdstLevel::MISSIONS,
+            Level::Reach(_) => HrLevel::MISSIONS,
+        }
+    }
+
+    // The mission after this one, None after the last mission.
+    pub fn next_mission(self) -> Option<Level> {
+        each_game!(self, level => level.next().filter(|next| next.is_mission()).map(Level::from))
+    }
+}
+
+impl core::fmt::Display for Level {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -1,14 +1,29 @@
+mod igt;
+mod level;
+pub mod memory;
+mod offsets;
+mod scan;
 mod splitter;
+pub mod timer;
+pub mod trace;
+#[cfg(test)]
+mod tests;
 
 extern crate alloc;
 
-use alloc::{format, string::ToString};
+use alloc::{collections::BTreeMap, format, string::ToString};
 use asr::file_format::pe::FileVersion;
 use asr::time::Duration;
 use asr::timer::TimerState;
-use asr::{deep_pointer::DeepPointer, print_message, settings::Gui, string::ArrayCString, watcher::Watcher, Process};
-use asr::settings::gui::Title;
+use asr::{print_message, settings::Gui, string::ArrayCString, watcher::Watcher, Process};
+use asr::settings::gui::{add_bool, add_title, Title, Widget};
+use igt::IgtAccumulator;
+use level::{H1Level, H2Level, H3Level, H4Level, HrLevel, Level, OdstLevel};
+use memory::{DeepPtr, MemorySource, Resolution};
+use scan::ScannedOffsets;
 use splitter::{H1Checklist, *};
+use timer::{AsrTimer, RecordingTimer, TimerBackend};
+use trace::{TraceError, TraceReader, TraceRecorder, TraceValue, WatcherVisitor};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum MCCGame {
@@ -26,6 +41,32 @@ impl core::fmt::Display for MCCGame {
         write!(f, "{:?}", self)
     }
 }
+impl MCCGame {
+    // Prefix of the game's watcher and pointer names.
+    pub fn prefix(self) -> Option<&'static str> {
+        match self {
+            MCCGame::Halo1 => Some("h1_"),
+            MCCGame::Halo2 => Some("h2_"),
+            MCCGame::Halo3 => Some("h3_"),
+            MCCGame::Halo4 => Some("h4_"),
+            MCCGame::ODST => Some("odst_"),
+            MCCGame::Reach => Some("hr_"),
+            MCCGame::Unknown => None,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            MCCGame::Halo1 => "Halo: CE",
+            MCCGame::Halo2 => "Halo 2",
+            MCCGame::Halo3 => "Halo 3",
+            MCCGame::Halo4 => "Halo 4",
+            MCCGame::ODST => "Halo 3: ODST",
+            MCCGame::Reach => "Halo: Reach",
+            MCCGame::Unknown => "Unknown",
+        }
+    }
+}
 impl From<u8> for MCCGame {
     fn from(value: u8) -> Self {
         match value {
@@ -40,25 +81,53 @@ impl From<u8> for MCCGame {
     }
 }
 
+// Halo 2 RTA load removal, see handle_h2_loading.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum H2LoadState {
+    #[default]
+    Playing,
+    // Loading screen up or no BSP loaded.
+    Loading,
+    // Level loaded, waiting for the fade in. Remembers whether the screen was faded out, in
+    // case the fade ends on a tick that doesn't count.
+    FadeIn { faded_out: bool },
+}
+
 #[derive(Default)]
 pub struct SplitterState {
     // Run tracking
     pub vars_reset: bool,
-    pub started_level: String,
+    pub started_level: Option<Level>,
+    // Name of the map the run started on, which tells custom maps apart as they have no Level
+    pub started_map: String,
     pub level_loaded: String,
     pub started_game: MCCGame,
     pub started_scene: u8,
+    // Values the status variables were last published with
+    pub game_var: Option<MCCGame>,
+    pub level_var: Option<Level>,
+    pub bsp_var: Option<u64>,
+    pub level_igt_var: Option<Duration>,
+    pub run_igt_var: Option<Duration>,
+    pub tick_rate: f64,
+    pub status_var: Option<String>,
+    pub route_var: Option<String>,
 
     // BSP tracking
     pub dirty_bsps_byte: Vec<u8>,
-    pub dirty_bsps_int: Vec<u32>,
-    pub dirty_bsps_long: Vec<u64>,
+    pub dirty_bsp_unloads: Vec<u8>,
+    // Level the H3/H4/ODST/Reach BSP masks were last seen in
+    pub bsp_level: Option<Level>,
 
     // Split flags
     pub loop_split: bool,
     pub force_split: bool,
     pub force_split2: bool,
 
+    // Level loaded from the main menu (sq_split)
+    pub sq_level: Option<Level>,
+    pub sq_pending: bool,
+
     // Halo 2 TGJ
     pub h2_tgj_ready_flag: bool,
     pub h2_tgj_ready_time: u32,
@@ -67,36 +136,52 @@ pub struct SplitterState {
     pub last_internal: bool,
     pub old_tick: i32,
     pub loading: bool,
+    pub h2_load_state: H2LoadState,
 
     // Multi-game
     pub multigame_pause: bool,
     pub multigame_time: Duration,
+    // Position of the game being played in the multigame route, and a game started out of
+    // the route's order.
+    pub route_game: usize,
+    pub route_out_of_order: Option<MCCGame>,
 
     // IGT tracking
     pub game_time: Duration,
-    pub ingame_time: u32,
-    pub level_time: u32,
-    pub pgcr_exists: bool,
+    pub igt: IgtAccumulator,
+
+    // H1 map check
+    pub h1_map_valid: Option<bool>,
+    pub h1_altered_map: Option<H1Level>,
+    pub map_valid_var: &'static str,
 
-    // H1 validity check
+    // H1 validity check. is_valid holds while every measured stretch of gameplay ran at 30
+    // ticks per second or slower, c_time is the real time measured and diff how far the tick
+    // counter got ahead of it.
     pub is_valid: bool,
     pub c_time: Duration,
     pub diff: Duration,
+    pub speed_ticks: u32,
+    pub speed_time: Duration,
+    pub speed_last_now: Option<Duration>,
 
     // H3 specific
     pub h3_reset_flag: bool,
 
     // Death counter
     pub death_counter: u32,
+    // Deaths per level in the order the levels were played, the last entry being the
+    // current level. Replaying the level straight after keeps adding to the same entry.
+    pub level_deaths: Vec<(Level, u32)>,
 }
 
 impl SplitterState {
     pub fn reset(&mut self) {
-        self.dirty_bsps_byte.clear();
-        self.dirty_bsps_int.clear();
-        self.dirty_bsps_long.clear();
+        self.clear_dirty_bsps();
+        self.bsp_level = None;
 
-        self.started_level = String::default();
+        self.started_level = None;
+        self.started_map = String::default();
         self.level_loaded = String::default();
         self.started_game = MCCGame::Unknown;
         self.started_scene = 0;
@@ -105,30 +190,40 @@ impl SplitterState {
         self.force_split = false;
         self.force_split2 = false;
 
+        self.sq_level = None;
+        self.sq_pending = false;
+
         self.h2_tgj_ready_flag = false;
         self.h2_tgj_ready_time = 0;
         self.last_internal = false;
         self.old_tick = -2;
         self.loading = false;
+        self.h2_load_state = H2LoadState::Playing;
         self.multigame_pause = false;
         self.multigame_time = Duration::ZERO;
+        self.route_game = 0;
+        self.route_out_of_order = None;
 
         self.game_time = Duration::ZERO;
-        self.ingame_time = 0;
-        self.level_time = 0;
-        self.pgcr_exists = false;
+        self.igt = IgtAccumulator::default();
 
+        self.h1_map_valid = None;
+        self.h1_altered_map = None;
+        self.map_valid_var = "";
         self.is_valid = false;
         self.c_time = Duration::ZERO;
         self.diff = Duration::ZERO;
+        self.speed_ticks = 0;
+        self.speed_time = Duration::ZERO;
+        self.speed_last_now = None;
 
         self.death_counter = 0;
+        self.level_deaths.clear();
     }
 
     pub fn clear_dirty_bsps(&mut self) {
         self.dirty_bsps_byte.clear();
-        self.dirty_bsps_int.clear();
-        self.dirty_bsps_long.clear();
+        self.dirty_bsp_unloads.clear();
     }
 
     pub fn add_dirty_bsp_byte(&mut self, bsp: u8) {
@@ -137,34 +232,13 @@ impl SplitterState {
         }
     }
 
-    pub fn add_dirty_bsp_int(&mut self, bsp: u32) {
-        if !self.dirty_bsps_int.contains(&bsp) {
-            self.dirty_bsps_int.push(bsp);
-        }
-    }
-
-    pub fn add_dirty_bsp_long(&mut self, bsp: u64) {
-        if !self.dirty_bsps_long.contains(&bsp) {
-            self.dirty_bsps_long.push(bsp);
-        }
-    }
-
     pub fn contains_dirty_bsp_byte(&self, bsp: u8) -> bool {
         self.dirty_bsps_byte.contains(&bsp)
     }
-
-    pub fn contains_dirty_bsp_int(&self, bsp: u32) -> bool {
-        self.dirty_bsps_int.contains(&bsp)
-    }
-
-    pub fn contains_dirty_bsp_long(&self, bsp: u64) -> bool {
-        self.dirty_bsps_long.contains(&bsp)
-    }
 }
 
-// Helper type for deep pointer which can store a chain of up to 8 ptrs
-type DeepPtr = DeepPointer<8>;
-
+// Only the WASM build is driven by the runtime. Host builds exist for replaying traces and tests.
+#[cfg(target_family = "wasm")]
 asr::async_main!(stable);
 //asr::panic_handler!();
 
@@ -204,6 +278,23 @@ macro_rules! changed_from_to {
     };
 }
 
+// Parses a level name watcher into the game's level. Returns from the enclosing function if
+// the name couldn't be read, and is None if the map isn't part of the campaign.
+macro_rules! level {
+    ($watcher:expr, $level:ident) => {
+        $level::parse(current!($watcher)?.as_bytes())
+    };
+}
+
+// Reads a pointer into the watcher of the same name, remembering how the read went.
+macro_rules! read {
+    ($state:ident, $pointers:ident, $source:ident, $name:ident) => {{
+        let resolution = $pointers.$name.resolve($source);
+        $state.pointer_health.entry(stringify!($name)).or_default().record(resolution.status());
+        $state.$name.update(resolution.ok());
+    }};
+}
+
 #[derive(Clone, Debug, Default, Eq, Gui, PartialEq)]
 enum LevelMode {
     /// Individual Level
@@ -214,6 +305,46 @@ enum LevelMode {
 }
 
 
+// One slot of the multigame route.
+#[derive(Clone, Copy, Debug, Default, Eq, Gui, PartialEq)]
+enum RouteGame {
+    /// -
+    #[default]
+    None,
+    /// Halo: CE
+    Halo1,
+    /// Halo 2
+    Halo2,
+    /// Halo 3
+    Halo3,
+    /// Halo 3: ODST
+    Odst,
+    /// Halo: Reach
+    Reach,
+    /// Halo 4
+    Halo4,
+}
+
+impl RouteGame {
+    const GAMES: [RouteGame; 6] = [RouteGame::Halo1, RouteGame::Halo2, RouteGame::Halo3, RouteGame::Odst, RouteGame::Reach, RouteGame::Halo4];
+
+    fn game(self) -> Option<MCCGame> {
+        match self {
+            RouteGame::None => None,
+            RouteGame::Halo1 => Some(MCCGame::Halo1),
+            RouteGame::Halo2 => Some(MCCGame::Halo2),
+            RouteGame::Halo3 => Some(MCCGame::Halo3),
+            RouteGame::Odst => Some(MCCGame::ODST),
+            RouteGame::Reach => Some(MCCGame::Reach),
+            RouteGame::Halo4 => Some(MCCGame::Halo4),
+        }
+    }
+
+    fn from_game(game: MCCGame) -> Self {
+        Self::GAMES.into_iter().find(|slot| slot.game() == Some(game)).unwrap_or_default()
+    }
+}
+
 #[derive(Gui)]
 struct Settings {
 
@@ -232,12 +363,34 @@ struct Settings {
     /// For TBx10 (or similiar memes). Requires Level Mode is set to Individual Level.
     loop_mode: bool,
 
+    /// Multigame route: 1st game
+    ///
+    /// The games of a multigame run in the order they're played, one per slot. Slots left on "-" are skipped, and leaving them all on "-"
+    /// lets the games be played in any order. The timer only starts on the first game, pauses at the end of each game, resumes only on the
+    /// start of the next game and stays paused after the last one. The "Multigame Route" variable shows where the run is.
+    /// Requires Level Mode is set to Full Game.
+    route_game_1: RouteGame,
+
+    /// --- 2nd game
+    route_game_2: RouteGame,
+
+    /// --- 3rd game
+    route_game_3: RouteGame,
+
+    /// --- 4th game
+    route_game_4: RouteGame,
+
+    /// --- 5th game
+    route_game_5: RouteGame,
+
+    /// --- 6th game
+    route_game_6: RouteGame,
+
     #[default = false]
     /// Split on unique "Loading... Done"'s
     ///
     /// Split on unique bsp loads ("Loading... Done") within levels.
-    /// You'll need to add a lot of extra splits for this option, see this spreadsheet for a count of how many per level of each game (outdated):
-    /// tinyurl.com/bspsplit
+    /// You'll need to add extra splits for this option, one per bsp load of each level picked under Bsp Splits.
     bsp_mode: bool,
 
     #[default = false]
@@ -285,6 +438,19 @@ struct Settings {
     /// You will probably have to set a starting offset in Edit Splits
     any_start: bool,
 
+    #[default = false]
+    /// --- Only start on stock maps (Halo: CE Only)
+    ///
+    /// Checks the loaded map against the stock map checksum and won't start the timer on modded or altered maps.
+    /// The "Map Valid" variable shows the result either way, and stays at "No" for the rest of a run that visited an altered map.
+    h1_map_check: bool,
+
+    /// Bsp Splits
+    ///
+    /// Levels to split on unique bsp loads in. Untick a level to leave out its bsp splits, e.g. to only split in Keyes and The Maw.
+    /// Only used when splitting on unique bsp loads.
+    bsp_levels: BspLevels,
+
     #[heading_level = 0]
     debug : Title,
 
@@ -299,9 +465,190 @@ struct Settings {
     ///
     /// Forces IGT sync regardless of game. Probably shouldn't use this
     igt_mode: bool,
+
+    #[default = false]
+    /// Pointer diagnostics
+    ///
+    /// Shows every value read from the game as a variable, along with whether its pointer resolved and how many ticks in a row it has failed.
+    /// Useful for finding out what broke after an MCC patch.
+    pointer_diagnostics: bool,
+
+    #[default = false]
+    /// Record watcher trace
+    ///
+    /// Writes every watcher value to the auto splitter log each tick they change.
+    /// Attach the log to bug reports so the run can be replayed.
+    record_trace: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            splitter_settings: Title::default(),
+            level_mode: LevelMode::FullGame,
+            loop_mode: false,
+            route_game_1: RouteGame::None,
+            route_game_2: RouteGame::None,
+            route_game_3: RouteGame::None,
+            route_game_4: RouteGame::None,
+            route_game_5: RouteGame::None,
+            route_game_6: RouteGame::None,
+            bsp_mode: false,
+            bsp_cache: false,
+            comp_splits: false,
+            h3_coop: false,
+            any_level: false,
+            menu_pause: true,
+            sq_split: false,
+            any_start: false,
+            h1_map_check: false,
+            bsp_levels: BspLevels::default(),
+            debug: Title::default(),
+            igt_add: false,
+            igt_mode: false,
+            pointer_diagnostics: false,
+            record_trace: false,
+        }
+    }
+}
+
+impl Settings {
+    // Whether BSP mode splits in the level.
+    fn bsp_splits(&self, level: Option<Level>) -> bool {
+        self.bsp_mode && level.is_some_and(|level| self.bsp_levels.enabled(level))
+    }
+
+    // The multigame route to follow, None if games can be played in any order or a full game
+    // run doesn't span games.
+    fn route(&self) -> Option<Vec<MCCGame>> {
+        if self.level_mode == LevelMode::IndividualLevel || self.any_level {
+            return None;
+        }
+        let route: Vec<_> = self.route_slots().iter().filter_map(|slot| slot.game()).collect();
+        (!route.is_empty()).then_some(route)
+    }
+
+    fn route_slots(&self) -> [RouteGame; 6] {
+        [self.route_game_1, self.route_game_2, self.route_game_3, self.route_game_4, self.route_game_5, self.route_game_6]
+    }
+
+    fn set_route(&mut self, games: &[MCCGame]) {
+        let mut games = games.iter().map(|&game| RouteGame::from_game(game));
+        for slot in [
+            &mut self.route_game_1,
+            &mut self.route_game_2,
+            &mut self.route_game_3,
+            &mut self.route_game_4,
+            &mut self.route_game_5,
+            &mut self.route_game_6,
+        ] {
+            *slot = games.next().unwrap_or_default();
+        }
+    }
+
+    // Options that affect splitting, as stored in trace headers.
+    fn trace_options(&mut self) -> [(&'static str, &mut bool); 12] {
+        [
+            ("loop_mode", &mut self.loop_mode),
+            ("bsp_mode", &mut self.bsp_mode),
+            ("bsp_cache", &mut self.bsp_cache),
+            ("comp_splits", &mut self.comp_splits),
+            ("h3_coop", &mut self.h3_coop),
+            ("any_level", &mut self.any_level),
+            ("menu_pause", &mut self.menu_pause),
+            ("sq_split", &mut self.sq_split),
+            ("any_start", &mut self.any_start),
+            ("h1_map_check", &mut self.h1_map_check),
+            ("igt_add", &mut self.igt_add),
+            ("igt_mode", &mut self.igt_mode),
+        ]
+    }
+
+    fn trace_line(&mut self) -> String {
+        let mut line = format!("settings level_mode={:?}", self.level_mode);
+        let route: Vec<_> = self.route_slots().iter().filter_map(|slot| slot.game()).map(|game| format!("{:?}", game)).collect();
+        if !route.is_empty() {
+            line.push_str(&format!(" multigame_route={}", route.join(",")));
+        }
+        for (name, value) in self.trace_options() {
+            line.push_str(&format!(" {}={}", name, *value as u8));
+        }
+        if !self.bsp_levels.disabled.is_empty() {
+            let keys: Vec<_> = self.bsp_levels.disabled.iter().map(|&level| BspLevels::key(level)).collect();
+            line.push_str(&format!(" bsp_levels_off={}", keys.join(",")));
+        }
+        line
+    }
+
+    fn apply_trace_option(&mut self, name: &str, value: &str) {
+        if name == "level_mode" {
+            self.level_mode = match value {
+                "IndividualLevel" => LevelMode::IndividualLevel,
+                _ => LevelMode::FullGame,
+            };
+        } else if name == "multigame_route" {
+            let games: Vec<_> = value
+                .split(',')
+                .filter_map(|name| RouteGame::GAMES.into_iter().filter_map(RouteGame::game).find(|game| format!("{:?}", game) == name))
+                .collect();
+            self.set_route(&games);
+        } else if name == "bsp_levels_off" {
+            self.bsp_levels.disabled = BspLevels::levels().filter(|&level| value.split(',').any(|key| key == BspLevels::key(level))).collect();
+        } else if let Some((_, option)) = self.trace_options().into_iter().find(|(n, _)| *n == name) {
+            *option = value == "1";
+        }
+    }
 }
 
+// Levels BSP mode splits in, as a toggle per level under a heading per game. Only levels
+// with entries in the BSP tables get a toggle.
 #[derive(Default)]
+struct BspLevels {
+    disabled: Vec<Level>,
+}
+
+impl BspLevels {
+    fn levels() -> impl Iterator<Item = Level> {
+        Level::all().filter(|&level| has_bsp_splits(level))
+    }
+
+    fn key(level: Level) -> String {
+        format!("bsp_{}{}", level.game().prefix().unwrap_or_default(), level.code())
+    }
+
+    fn enabled(&self, level: Level) -> bool {
+        !self.disabled.contains(&level)
+    }
+}
+
+impl Widget for BspLevels {
+    type Args = ();
+
+    fn register(key: &str, description: &str, _args: ()) -> Self {
+        add_title(key, description, 1);
+
+        let mut disabled = Vec::new();
+        let mut game = MCCGame::Unknown;
+        for level in Self::levels() {
+            if level.game() != game {
+                game = level.game();
+                add_title(&format!("{}_{}", key, game), game.title(), 2);
+            }
+            if !add_bool(&Self::key(level), level.name(), true) {
+                disabled.push(level);
+            }
+        }
+        Self { disabled }
+    }
+
+    fn update_from(&mut self, settings_map: &asr::settings::Map, _key: &str, _args: ()) {
+        self.disabled = Self::levels()
+            .filter(|&level| settings_map.get(&Self::key(level)).and_then(|value| value.get_bool()) == Some(false))
+            .collect();
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
 struct GameDLLs {
     exe_mcc: asr::Address,
     dll_halo1: asr::Address,
@@ -312,6 +659,54 @@ struct GameDLLs {
     dll_halo_reach: asr::Address,
 }
 
+// Ticks between looking up the game DLLs. Loading a game takes far longer than this.
+const MODULE_SCAN_TICKS: u32 = 60;
+
+impl GameDLLs {
+    fn find(process: &Process, exe_mcc: asr::Address) -> Self {
+        Self {
+            exe_mcc,
+            dll_halo1: process.get_module_address("halo1.dll").unwrap_or_default(),
+            dll_halo2: process.get_module_address("halo2.dll").unwrap_or_default(),
+            dll_halo3: process.get_module_address("halo3.dll").unwrap_or_default(),
+            dll_halo4: process.get_module_address("halo4.dll").unwrap_or_default(),
+            dll_halo3_odst: process.get_module_address("halo3odst.dll").unwrap_or_default(),
+            dll_halo_reach: process.get_module_address("haloreach.dll").unwrap_or_default(),
+        }
+    }
+
+    // Whether the game's DLL has been found, always true when no game is running.
+    fn is_loaded(&self, game: MCCGame) -> bool {
+        let dll = match game {
+            MCCGame::Halo1 => self.dll_halo1,
+            MCCGame::Halo2 => self.dll_halo2,
+            MCCGame::Halo3 => self.dll_halo3,
+            MCCGame::Halo4 => self.dll_halo4,
+            MCCGame::ODST => self.dll_halo3_odst,
+            MCCGame::Reach => self.dll_halo_reach,
+            MCCGame::Unknown => return true,
+        };
+        !dll.is_null()
+    }
+
+    // Publishes the bases that differ from the last ones published.
+    fn set_timer_vars(&self, last: Option<&GameDLLs>, timer: &mut impl TimerBackend) {
+        let vars = [
+            ("dll_h1", self.dll_halo1, last.map(|l| l.dll_halo1)),
+            ("dll_h2", self.dll_halo2, last.map(|l| l.dll_halo2)),
+            ("dll_h3", self.dll_halo3, last.map(|l| l.dll_halo3)),
+            ("dll_h4", self.dll_halo4, last.map(|l| l.dll_halo4)),
+            ("dll_h3_odst", self.dll_halo3_odst, last.map(|l| l.dll_halo3_odst)),
+            ("dll_reach", self.dll_halo_reach, last.map(|l| l.dll_halo_reach)),
+        ];
+        for (key, base, last) in vars {
+            if last != Some(base) {
+                timer.set_variable(key, &base.to_string());
+            }
+        }
+    }
+}
+
 #[derive(Default)]
 struct GamePointers {
     // State - MCC
@@ -381,8 +776,80 @@ struct GamePointers {
     fadescale: f64,
 }
 
+#[cfg(test)]
+impl GamePointers {
+    // Every pointer by name, in declaration order.
+    fn named(&self) -> [(&'static str, &DeepPtr); 48] {
+        [
+            // State - MCC
+            ("mcc_loadindicator", &self.mcc_loadindicator),
+            ("mcc_menuindicator", &self.mcc_menuindicator),
+            ("mcc_pauseindicator", &self.mcc_pauseindicator),
+            ("mcc_pgcrindicator", &self.mcc_pgcrindicator),
+            ("mcc_gameindicator", &self.mcc_gameindicator),
+            ("mcc_igt_float", &self.mcc_igt_float),
+            ("mcc_comptimerstate", &self.mcc_comptimerstate),
+            // State - Halo 1
+            ("h1_tickcounter", &self.h1_tickcounter),
+            ("h1_igt", &self.h1_igt),
+            ("h1_bspstate", &self.h1_bspstate),
+            ("h1_levelname", &self.h1_levelname),
+            ("h1_gamewon", &self.h1_gamewon),
+            ("h1_cinematic", &self.h1_cinematic),
+            ("h1_cutsceneskip", &self.h1_cutsceneskip),
+            ("h1_xpos", &self.h1_xpos),
+            ("h1_ypos", &self.h1_ypos),
+            ("h1_fadetick", &self.h1_fadetick),
+            ("h1_fadelength", &self.h1_fadelength),
+            ("h1_fadebyte", &self.h1_fadebyte),
+            ("h1_deathflag", &self.h1_deathflag),
+            ("h1_checksum", &self.h1_checksum),
+            ("h1_aflags", &self.h1_aflags),
+            // State - Halo 2
+            ("h2_levelname", &self.h2_levelname),
+            ("h2_igt", &self.h2_igt),
+            ("h2_bspstate", &self.h2_bspstate),
+            ("h2_deathflag", &self.h2_deathflag),
+            ("h2_tickcounter", &self.h2_tickcounter),
+            ("h2_graphics", &self.h2_graphics),
+            ("h2_fadebyte", &self.h2_fadebyte),
+            ("h2_letterbox", &self.h2_letterbox),
+            ("h2_xpos", &self.h2_xpos),
+            ("h2_ypos", &self.h2_ypos),
+            ("h2_fadetick", &self.h2_fadetick),
+            ("h2_fadelength", &self.h2_fadelength),
+            // State - Halo 3
+            ("h3_levelname", &self.h3_levelname),
+            ("h3_theatertime", &self.h3_theatertime),
+            ("h3_tickcounter", &self.h3_tickcounter),
+            ("h3_bspstate", &self.h3_bspstate),
+            ("h3_deathflag", &self.h3_deathflag),
+            // State - Halo Reach
+            ("hr_levelname", &self.hr_levelname),
+            ("hr_bspstate", &self.hr_bspstate),
+            ("hr_deathflag", &self.hr_deathflag),
+            // State - ODST
+            ("odst_levelname", &self.odst_levelname),
+            ("odst_streets", &self.odst_streets),
+            ("odst_bspstate", &self.odst_bspstate),
+            ("odst_deathflag", &self.odst_deathflag),
+            // State - Halo 4
+            ("h4_levelname", &self.h4_levelname),
+            ("h4_bspstate", &self.h4_bspstate),
+        ]
+    }
+}
+
 #[derive(Default)]
 struct GameState {
+    mcc_version: String,
+    // Per-version values from the offset table
+    fadescale: f64,
+    h1_checklist: H1Checklist,
+
+    // How each pointer has been reading, by watcher name
+    pointer_health: BTreeMap<&'static str, PointerHealth>,
+
     // MCC
     mcc_loadindicator: Watcher<u8>,
     mcc_menuindicator: Watcher<u8>,
@@ -446,555 +913,490 @@ struct GameState {
     h4_bspstate: Watcher<u64>,
 }
 
-fn update_game_pointers(is_winstore: bool, mcc_version: FileVersion, dlls: &GameDLLs, ptrs: &mut GamePointers) {
-    if is_winstore && mcc_version.minor_version < 3272 {
-        panic!("Invalid WinStore version should have been handled!");
+impl GameState {
+    pub fn visit_watchers(&mut self, visitor: &mut impl WatcherVisitor) {
+        // MCC
+        visitor.visit("mcc_loadindicator", &mut self.mcc_loadindicator);
+        visitor.visit("mcc_menuindicator", &mut self.mcc_menuindicator);
+        visitor.visit("mcc_pauseindicator", &mut self.mcc_pauseindicator);
+        visitor.visit("mcc_pgcrindicator", &mut self.mcc_pgcrindicator);
+        visitor.visit("mcc_gameindicator", &mut self.mcc_gameindicator);
+        visitor.visit("mcc_igt_float", &mut self.mcc_igt_float);
+        visitor.visit("mcc_comptimerstate", &mut self.mcc_comptimerstate);
+
+        // Halo 1
+        visitor.visit("h1_tickcounter", &mut self.h1_tickcounter);
+        visitor.visit("h1_igt", &mut self.h1_igt);
+        visitor.visit("h1_bspstate", &mut self.h1_bspstate);
+        visitor.visit("h1_levelname", &mut self.h1_levelname);
+        visitor.visit("h1_gamewon", &mut self.h1_gamewon);
+        visitor.visit("h1_cinematic", &mut self.h1_cinematic);
+        visitor.visit("h1_cutsceneskip", &mut self.h1_cutsceneskip);
+        visitor.visit("h1_xpos", &mut self.h1_xpos);
+        visitor.visit("h1_ypos", &mut self.h1_ypos);
+        visitor.visit("h1_fadetick", &mut self.h1_fadetick);
+        visitor.visit("h1_fadelength", &mut self.h1_fadelength);
+        visitor.visit("h1_fadebyte", &mut self.h1_fadebyte);
+        visitor.visit("h1_deathflag", &mut self.h1_deathflag);
+        visitor.visit("h1_checksum", &mut self.h1_checksum);
+        visitor.visit("h1_aflags", &mut self.h1_aflags);
+
+        // Halo 2
+        visitor.visit("h2_levelname", &mut self.h2_levelname);
+        visitor.visit("h2_igt", &mut self.h2_igt);
+        visitor.visit("h2_bspstate", &mut self.h2_bspstate);
+        visitor.visit("h2_deathflag", &mut self.h2_deathflag);
+        visitor.visit("h2_tickcounter", &mut self.h2_tickcounter);
+        visitor.visit("h2_graphics", &mut self.h2_graphics);
+        visitor.visit("h2_fadebyte", &mut self.h2_fadebyte);
+        visitor.visit("h2_letterbox", &mut self.h2_letterbox);
+        visitor.visit("h2_xpos", &mut self.h2_xpos);
+        visitor.visit("h2_ypos", &mut self.h2_ypos);
+        visitor.visit("h2_fadetick", &mut self.h2_fadetick);
+        visitor.visit("h2_fadelength", &mut self.h2_fadelength);
+
+        // Halo 3
+        visitor.visit("h3_levelname", &mut self.h3_levelname);
+        visitor.visit("h3_theatertime", &mut self.h3_theatertime);
+        visitor.visit("h3_tickcounter", &mut self.h3_tickcounter);
+        visitor.visit("h3_bspstate", &mut self.h3_bspstate);
+        visitor.visit("h3_deathflag", &mut self.h3_deathflag);
+
+        // Halo Reach
+        visitor.visit("hr_levelname", &mut self.hr_levelname);
+        visitor.visit("hr_bspstate", &mut self.hr_bspstate);
+        visitor.visit("hr_deathflag", &mut self.hr_deathflag);
+
+        // ODST
+        visitor.visit("odst_levelname", &mut self.odst_levelname);
+        visitor.visit("odst_streets", &mut self.odst_streets);
+        visitor.visit("odst_bspstate", &mut self.odst_bspstate);
+        visitor.visit("odst_deathflag", &mut self.odst_deathflag);
+
+        // Halo 4
+        visitor.visit("h4_levelname", &mut self.h4_levelname);
+        visitor.visit("h4_bspstate", &mut self.h4_bspstate);
+    }
+
+    pub fn current_game(&self) -> MCCGame {
+        current!(self.mcc_gameindicator).map_or(MCCGame::Unknown, MCCGame::from)
+    }
+
+    // Forgets the values of every game's watchers except the current one's.
+    pub fn clear_inactive_games(&mut self) {
+        let prefix = self.current_game().prefix();
+        let active = |name: &str| name.starts_with("mcc_") || prefix.is_some_and(|prefix| name.starts_with(prefix));
+        self.visit_watchers(&mut WatcherClearer { active });
+        self.pointer_health.retain(|name, _| active(name));
+    }
+
+    // Names of the pointers that read as given on their last read, keeping to MCC's and the
+    // game's own.
+    fn unresolved(&self, game: MCCGame, resolution: Resolution<()>) -> Vec<&'static str> {
+        self.pointer_health
+            .iter()
+            .filter(|(name, _)| name.starts_with("mcc_") || game.prefix().is_some_and(|prefix| name.starts_with(prefix)))
+            .filter(|(_, h)| h.resolution == resolution)
+            .map(|(name, _)| *name)
+            .collect()
     }
+}
 
-    *ptrs = GamePointers::default();
+#[derive(Clone, Copy)]
+struct PointerHealth {
+    // How the last read went
+    resolution: Resolution<()>,
+    // Ticks in a row the pointer has failed to resolve
+    failed_ticks: u32,
+}
 
-    match mcc_version.minor_version {
-        2448 => {
-            ptrs.h1_checklist = H1Checklist {
-                a10: 2495112808,
-                a30: 1196246201,
-                a50: 3037603536,
-                b30: 682311759,
-                b40: 326064131,
-                c10: 645721511,
-                c20: 540616268,
-                c40: 1500399674,
-                d20: 2770760039,
-                d40: 1695151528,
-            };
-            ptrs.fadescale = 0.183;
-        }
-        2645 => {
-            ptrs.h1_checklist = H1Checklist {
-                a10: 4031641132,
-                a30: 1497905037,
-                a50: 2613596386,
-                b30: 4057206713,
-                b40: 2439716616,
-                c10: 2597150717,
-                c20: 1656675814,
-                c40: 1573304389,
-                d20: 1507739304,
-                d40: 2038583061,
-            };
-            ptrs.fadescale = 0.067;
-        }
-        2904 => {
-            ptrs.h1_checklist = H1Checklist {
-                a10: 89028072,
-                a30: 1083179843,
-                a50: 2623582826,
-                b30: 1895318681,
-                b40: 1935970024,
-                c10: 974037405,
-                c20: 714510620,
-                c40: 2859044941,
-                d20: 1178559651,
-                d40: 3253884125,
-            };
-            ptrs.fadescale = 0.067;
-        }
-        2969 => {
-            ptrs.h1_checklist = H1Checklist {
-                a10: 2023477633,
-                a30: 1197744442,
-                a50: 522123179,
-                b30: 2022995318,
-                b40: 4112928798,
-                c10: 4250424451,
-                c20: 1165450382,
-                c40: 2733116763,
-                d20: 1722772470,
-                d40: 3775314541,
-            };
-            ptrs.fadescale = 0.067;
-        }
-        3073 => {
-            ptrs.h1_checklist = H1Checklist {
-                a10: 3589325267,
-                a30: 3649693672,
-                a50: 1186687708,
-                b30: 1551598635,
-                b40: 1100623455,
-                c10: 3494823778,
-                c20: 2445460720,
-                c40: 3759075146,
-                d20: 3442848200,
-                d40: 1751474532,
-            };
-            ptrs.fadescale = 0.067;
-        }
-        3272 | 3385 | 3495 | 3498 | 3528 | _ /* Unknown Version, attempt to use latest */ => {
-            ptrs.h1_checklist = H1Checklist {
-                a10: 1731967100,
-                a30: 2334900663,
-                a50: 2345488806,
-                b30: 389775619,
-                b40: 232036917,
-                c10: 3544120777,
-                c20: 2188406812,
-                c40: 687169669,
-                d20: 485256620,
-                d40: 1783204841,
-            };
-            ptrs.fadescale = 0.067;
+impl Default for PointerHealth {
+    fn default() -> Self {
+        Self {
+            resolution: Resolution::Missing,
+            failed_ticks: 0,
         }
     }
+}
 
-    let v = mcc_version.minor_version;
-
-    // MCC
-    let (mcc_menustate, mcc_menu_offset, mcc_pause_offset, mcc_pgcr_offset, mcc_gameindicator, mcc_igt_float, mcc_comptimerstate, mcc_comptimer_offset) =
-        match v {
-            2448 => (0x3A24FC4, 0x11, 0xA, 0xB, 0x3A253A0, 0x3A25188, 0x3A254B0, 0x1A4),
-            2645 => (0x3B80E64, 0x11, 0xB, 0xC, 0x3B81270, 0x3B80FF8, 0x3B81380, 0x1A4),
-            2904 => (0x3F7BAAD, 0x8, 0x5, 0x6, 0x3F7C380, 0x3F7C33C, 0x3F7C358, 0x1A4),
-            2969 => (0x3F9446C, 0x11, 0xB, 0xC, 0x3F94E90, 0x3F94F88, 0x3F94F60, 0x1A4),
-            3073 => (0x401B76C, 0x11, 0xB, 0xC, 0x401C1C0, 0x401C204, 0x401C1D8, 0x1AC),
-            3272 => {
-                if is_winstore {
-                    (0x3E4C034, 0x11, 0xB, 0xC, 0x3E4CA78, 0x3E4CB30, 0x3E4CA90, 0x1AC)
-                } else {
-                    (0x3FFDAA4, 0x11, 0xB, 0xC, 0x3FFE4D8, 0x3FFE590, 0x3FFE4F0, 0x1AC)
-                }
-            }
-            3385 => {
-                if is_winstore {
-                    (0x3E4B034, 0x11, 0xB, 0xC, 0x3E4BA68, 0x3E4BB28, 0x3E4BA80, 0x1AC)
-                } else {
-                    (0x3FFCA94, 0x11, 0xB, 0xC, 0x3FFD4C8, 0x3FFD588, 0x3FFD4E0, 0x1AC)
-                }
-            }
-            v if v >= 3528 => {
-                if is_winstore {
-                    (0x3E4EFE4, 0x11, 0xB, 0xC, 0x3E4FAB8, 0x3E4FAA4, 0x3E4FAF8, 0x1AC)
-                } else {
-                    (0x4000B8C, 0x11, 0xB, 0xC, 0x4001658, 0x4001644, 0x4001698, 0x1AC)
-                }
-            }
-            _ => (0, 0, 0, 0, 0, 0, 0, 0),
+impl PointerHealth {
+    fn record(&mut self, resolution: Resolution<()>) {
+        self.failed_ticks = match resolution {
+            Resolution::Resolved(()) => 0,
+            _ => self.failed_ticks + 1,
         };
-
-    if mcc_menustate != 0 {
-        ptrs.mcc_loadindicator = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc_menustate]);
-        ptrs.mcc_menuindicator = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc_menustate + mcc_menu_offset]);
-        ptrs.mcc_pauseindicator = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc_menustate + mcc_pause_offset]);
-        ptrs.mcc_pgcrindicator = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc_menustate + mcc_pgcr_offset]);
-    }
-    if mcc_gameindicator != 0 {
-        ptrs.mcc_gameindicator = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc_gameindicator, 0x0]);
-    }
-    if mcc_igt_float != 0 {
-        ptrs.mcc_igt_float = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc_igt_float]);
-    }
-    if mcc_comptimerstate != 0 {
-        ptrs.mcc_comptimerstate = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc_comptimerstate, mcc_comptimer_offset]);
+        self.resolution = resolution;
     }
+}
 
-    // Halo 1
-    let (h1_tickcounter, h1_igt, h1_bspstate, h1_globals, h1_map, h1_cinflags, h1_coords, h1_fade) = match v {
-        2448 => (0x2B58A24, 0x2AF477C, 0x19F0400, 0x2AF10D0, 0x2A4BC04, 0x2AF1868, 0x2A57E74, 0x2B81CE8),
-        2645 => (0x2B5FC04, 0x2AFB954, 0x19F748C, 0x2AF8240, 0x2A52D84, 0x2AF89B8, 0x2A5EFF4, 0x2B88E58),
-        2904 => (0x2B88764, 0x2E7A354, 0x1B661CC, 0x2B611A0, 0x2D66A24, 0x2E773D8, 0x2D7313C, 0x2E7F868),
-        2969 => (0x2CECFD4, 0x14872C0, 0x1CE4920, 0x2CC5860, 0x2EEB024, 0x2FFBD28, 0x1DF5FF8, 0x30041A8),
-        3073 => (0x2CEBD34, 0x3008134, 0x1CECDFC, 0x2CA0780, 0x2C9F7C4, 0x3005198, 0x2F00954, 0x300D678),
-        3272 => (0x2B6F5E4, 0x2EA31C4, 0x1B860A4, 0x2B23700, 0x2B22744, 0x2EA01F8, 0x2D9B9C4, 0x2EA8708),
-        v if v >= 3385 => (0x2B6F5E4, 0x2EA31D4, 0x1B860A4, 0x2B23700, 0x2B22744, 0x2EA0208, 0x2D9B9C4, 0x2EA8718),
-        _ => (0, 0, 0, 0, 0, 0, 0, 0),
-    };
-    if !dlls.dll_halo1.is_null() {
-        if h1_tickcounter != 0 {
-            ptrs.h1_tickcounter = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_tickcounter]);
-        }
-        if h1_igt != 0 {
-            ptrs.h1_igt = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_igt]);
-        }
-        if h1_bspstate != 0 {
-            ptrs.h1_bspstate = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_bspstate]);
-        }
-        if h1_map != 0 {
-            ptrs.h1_levelname = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_map + 0x20]);
-            ptrs.h1_checksum = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_map + 0x64]);
-            ptrs.h1_aflags = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_map + 0x68]);
-        }
-        if h1_globals != 0 {
-            ptrs.h1_gamewon = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_globals + 0x1]);
-            ptrs.h1_deathflag = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_globals + 0x17]);
-        }
-        if h1_cinflags != 0 {
-            ptrs.h1_cinematic = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_cinflags, 0x0A]);
-            ptrs.h1_cutsceneskip = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_cinflags, 0x0B]);
-        }
-        if h1_coords != 0 {
-            ptrs.h1_xpos = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_coords]);
-            ptrs.h1_ypos = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_coords + 0x4]);
-        }
-        if h1_fade != 0 {
-            ptrs.h1_fadetick = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_fade, 0x3C0]);
-            ptrs.h1_fadelength = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_fade, 0x3C4]);
-            ptrs.h1_fadebyte = DeepPtr::new_64bit(dlls.dll_halo1, &[h1_fade, 0x3C6]);
+struct WatcherClearer<F> {
+    active: F,
+}
+
+impl<F: Fn(&str) -> bool> WatcherVisitor for WatcherClearer<F> {
+    fn visit<T: TraceValue>(&mut self, name: &'static str, watcher: &mut Watcher<T>) {
+        if !(self.active)(name) {
+            watcher.pair = None;
         }
     }
+}
 
-    // Halo 2
-    let (h2_levelname, h2_igt, h2_bspstate, h2_deathflag, h2_tickcounter, h2_graphics, h2_cinflags, h2_coords, h2_fade) = match v {
-        2448 => (0xE63FB3, 0xE22F40, 0xCD7D74, 0xDA6140, 0xE63144, 0xCFB918, 0x143ACA0, 0xDA5CD8, 0x13DFC58),
-        2645 => (0xD42E68, 0x1475C10, 0xCA4D74, 0xD52800, 0x14B5DE4, 0xCC74A8, 0x15186A0, 0xD523A8, 0x14BD450),
-        2904 => (0xD4ABF8, 0x147D9F0, 0xCACD74, 0xD5A5A0, 0x14BDBC4, 0xCCF280, 0x1520498, 0xD5A148, 0x14C5228),
-        2969 => (0xD54498, 0x14872C0, 0xCB2D74, 0xD63E80, 0x14C7494, 0xCD8998, 0x14D9448, 0xD63A28, 0x14CEB68),
-        3073 => (0xE6ED78, 0x15A1BA0, 0xDF7D74, 0xE7E760, 0x15E1D74, 0xE1F178, 0x15F42B8, 0xE7E308, 0xE7E308),
-        3272 | 3385 => (0xE6FE68, 0x15A2EA0, 0xDF8D74, 0xE7FA50, 0x15E3074, 0xE20278, 0x15F5788, 0xE7F5E8, 0x15EA778),
-        v if v >= 3528 => (0xE70E68, 0x15A3EA0, 0xDF9D74, 0xE80A50, 0x15E4074, 0xE21278, 0x15F6788, 0xE805E8, 0x15EB778),
-        _ => (0, 0, 0, 0, 0, 0, 0, 0, 0),
-    };
-    if !dlls.dll_halo2.is_null() {
-        if h2_levelname != 0 {
-            ptrs.h2_levelname = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_levelname]);
-        }
-        if h2_igt != 0 {
-            ptrs.h2_igt = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_igt]);
-        }
-        if h2_bspstate != 0 {
-            ptrs.h2_bspstate = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_bspstate]);
-        }
-        if h2_deathflag != 0 {
-            ptrs.h2_deathflag = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_deathflag, -0xEFi64 as u64]);
-        }
-        if h2_tickcounter != 0 {
-            ptrs.h2_tickcounter = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_tickcounter]);
-        }
-        if h2_graphics != 0 {
-            ptrs.h2_graphics = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_graphics]);
-        }
-        if h2_cinflags != 0 {
-            ptrs.h2_fadebyte = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_cinflags, -0x92Ei64 as u64]);
-            ptrs.h2_letterbox = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_cinflags, -0x938i64 as u64]);
-        }
-        if h2_coords != 0 {
-            ptrs.h2_xpos = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_coords]);
-            ptrs.h2_ypos = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_coords + 0x4]);
-        }
-        if h2_fade != 0 {
-            ptrs.h2_fadetick = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_fade, 0x0]);
-            ptrs.h2_fadelength = DeepPtr::new_64bit(dlls.dll_halo2, &[h2_fade, 0x4]);
+fn update_game_pointers(is_winstore: bool, mcc_version: FileVersion, dlls: &GameDLLs, scanned: &ScannedOffsets, ptrs: &mut GamePointers) {
+    *ptrs = GamePointers::default();
+
+    let Some(offsets) = offsets::closest(mcc_version.minor_version) else { return };
+
+    // Builds newer than the table use the latest offsets, with anything found by signature swapped
+    // in. Those are only trusted once every global was found, otherwise every pointer is missing.
+    let patched;
+    let offsets = if offsets.version != mcc_version.minor_version {
+        if !scanned.found_all() {
+            return;
         }
-    }
+        patched = scanned.apply(offsets);
+        &patched
+    } else {
+        offsets
+    };
+    // Builds that never ran on the WinStore leave every pointer missing
+    let Some(mcc) = offsets.mcc(is_winstore) else { return };
+
+    ptrs.h1_checklist = offsets.h1.checklist;
+    ptrs.fadescale = offsets.fadescale;
+
+    // MCC
+    ptrs.mcc_loadindicator = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc.menustate]);
+    ptrs.mcc_menuindicator = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc.menustate + mcc.menu]);
+    ptrs.mcc_pauseindicator = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc.menustate + mcc.pause]);
+    ptrs.mcc_pgcrindicator = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc.menustate + mcc.pgcr]);
+    ptrs.mcc_gameindicator = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc.gameindicator, 0x0]);
+    ptrs.mcc_igt_float = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc.igt_float]);
+    ptrs.mcc_comptimerstate = DeepPtr::new_64bit(dlls.exe_mcc, &[mcc.comptimerstate, mcc.comptimer]);
+
+    // Halo 1
+    let h1 = &offsets.h1;
+    ptrs.h1_tickcounter = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.tickcounter]);
+    ptrs.h1_igt = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.igt]);
+    ptrs.h1_bspstate = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.bspstate]);
+    ptrs.h1_levelname = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.map + 0x20]);
+    ptrs.h1_checksum = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.map + 0x64]);
+    ptrs.h1_aflags = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.map + 0x68]);
+    ptrs.h1_gamewon = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.globals + 0x1]);
+    ptrs.h1_deathflag = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.globals + 0x17]);
+    ptrs.h1_cinematic = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.cinflags, 0x0A]);
+    ptrs.h1_cutsceneskip = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.cinflags, 0x0B]);
+    ptrs.h1_xpos = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.coords]);
+    ptrs.h1_ypos = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.coords + 0x4]);
+    ptrs.h1_fadetick = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.fade, 0x3C0]);
+    ptrs.h1_fadelength = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.fade, 0x3C4]);
+    ptrs.h1_fadebyte = DeepPtr::new_64bit(dlls.dll_halo1, &[h1.fade, 0x3C6]);
+
+    // Halo 2
+    let h2 = &offsets.h2;
+    ptrs.h2_levelname = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.levelname]);
+    ptrs.h2_igt = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.igt]);
+    ptrs.h2_bspstate = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.bspstate]);
+    ptrs.h2_deathflag = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.deathflag, -0xEFi64 as u64]);
+    ptrs.h2_tickcounter = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.tickcounter]);
+    ptrs.h2_graphics = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.graphics]);
+    ptrs.h2_fadebyte = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.cinflags, -0x92Ei64 as u64]);
+    ptrs.h2_letterbox = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.cinflags, -0x938i64 as u64]);
+    ptrs.h2_xpos = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.coords]);
+    ptrs.h2_ypos = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.coords + 0x4]);
+    ptrs.h2_fadetick = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.fade, 0x0]);
+    ptrs.h2_fadelength = DeepPtr::new_64bit(dlls.dll_halo2, &[h2.fade, 0x4]);
 
     // Halo 3
-    let (h3_levelname, h3_theatertime, h3_tickcounter, h3_bspstate, h3_deathflag_offset1, h3_deathflag_offset2) = match v {
-        2448 => (0x1D2C460, 0x1DDC3BC, 0x2961E0C, 0x9F3EF0, 0x1CB15C8, 0x1051D),
-        2645 => (0x1E0D358, 0x1EDAA9C, 0x2A1F34C, 0x9A4BA0, 0x1D91E68, 0x1077D),
-        2904 => (0x1E092E8, 0x1E9B4BC, 0x29E194C, 0x99FCA0, 0x1D8DF48, 0x1073D),
-        2969 => (0x1EABB78, 0x1F3DD5C, 0x2B4178C, 0xA41D20, 0x1E30758, 0x1074D),
-        3073 | 3272 | 3385 if v == 3073 => (0x1E92AB8, 0x1F2084C, 0x2B34F2C, 0xA39220, 0x1E19C98, 0xFDCD),
-        3272 | 3385 => (0x20A8118, 0x2135F70, 0x2D3C04C, 0xA4E170, 0x202F2D8, 0xFDCD),
-        v if v >= 3528 => (0x20A9118, 0x2136F70, 0x2D3D04C, 0xA4F170, 0x20302D8, 0xFDCD),
-        _ => (0, 0, 0, 0, 0, 0),
-    };
-    if !dlls.dll_halo3.is_null() {
-        if h3_levelname != 0 {
-            ptrs.h3_levelname = DeepPtr::new_64bit(dlls.dll_halo3, &[h3_levelname]);
-        }
-        if h3_theatertime != 0 {
-            ptrs.h3_theatertime = DeepPtr::new_64bit(dlls.dll_halo3, &[h3_theatertime]);
-        }
-        if h3_tickcounter != 0 {
-            ptrs.h3_tickcounter = DeepPtr::new_64bit(dlls.dll_halo3, &[h3_tickcounter]);
-        }
-        if h3_bspstate != 0 {
-            ptrs.h3_bspstate = DeepPtr::new_64bit(dlls.dll_halo3, &[h3_bspstate, 0x2C]);
-        }
-        if h3_deathflag_offset1 != 0 {
-            ptrs.h3_deathflag = DeepPtr::new_64bit(dlls.dll_halo3, &[h3_deathflag_offset1, h3_deathflag_offset2]);
-        }
-    }
+    let h3 = &offsets.h3;
+    ptrs.h3_levelname = DeepPtr::new_64bit(dlls.dll_halo3, &[h3.levelname]);
+    ptrs.h3_theatertime = DeepPtr::new_64bit(dlls.dll_halo3, &[h3.theatertime]);
+    ptrs.h3_tickcounter = DeepPtr::new_64bit(dlls.dll_halo3, &[h3.tickcounter]);
+    ptrs.h3_bspstate = DeepPtr::new_64bit(dlls.dll_halo3, &[h3.bspstate, 0x2C]);
+    ptrs.h3_deathflag = DeepPtr::new_64bit(dlls.dll_halo3, &[h3.deathflag, h3.deathflag_offset]);
 
     // Reach
-    let (hr_levelname, hr_bspstate, hr_deathflag_offset1, hr_deathflag_offset2) = match v {
-        2448 => (0x2868777, 0x36778E0, 0xEEFEB0, 0x544249),
-        2645 => (0x2907107, 0x3716270, 0xEEF330, 0x594249),
-        2904 | 2969 if v == 2904 => (0x28A4C3F, 0x3719E24, 0x23CC7D8, 0x1F419),
-        2969 => (0x2A39A8F, 0x3BB32A0, 0x2514A88, 0x1F419),
-        3073 | 3272 | 3385 if v == 3073 => (0x2A2F6D7, 0x3B9C020, 0x250B808, 0x1ED09),
-        3272 | 3385 => (0x2A1F587, 0x4E2FBA8, 0x24FB708, 0x1ED09),
-        v if v >= 3528 => (0x2A1F527, 0x4E2FB28, 0x24FB5F0, 0x1ED09),
-        _ => (0, 0, 0, 0),
-    };
-    if !dlls.dll_halo_reach.is_null() {
-        if hr_levelname != 0 {
-            ptrs.hr_levelname = DeepPtr::new_64bit(dlls.dll_halo_reach, &[hr_levelname]);
-        }
-        if hr_bspstate != 0 {
-            ptrs.hr_bspstate = DeepPtr::new_64bit(dlls.dll_halo_reach, &[hr_bspstate]);
-        }
-        if hr_deathflag_offset1 != 0 {
-            ptrs.hr_deathflag = DeepPtr::new_64bit(dlls.dll_halo_reach, &[hr_deathflag_offset1, hr_deathflag_offset2]);
-        }
-    }
+    let hr = &offsets.hr;
+    ptrs.hr_levelname = DeepPtr::new_64bit(dlls.dll_halo_reach, &[hr.levelname]);
+    ptrs.hr_bspstate = DeepPtr::new_64bit(dlls.dll_halo_reach, &[hr.bspstate]);
+    ptrs.hr_deathflag = DeepPtr::new_64bit(dlls.dll_halo_reach, &[hr.deathflag, hr.deathflag_offset]);
 
     // ODST
-    let (odst_levelname, odst_streets, odst_bspstate, odst_deathflag) = match v {
-        _v if dlls.dll_halo3_odst.is_null() => (0, 0, 0, 0),
-        2448 => (0x1CDF200, 0x1DB2568, 0x2E46964, 0xE8520C),
-        2645 => (0x2020CA8, 0x2116FD8, 0x2F91A9C, 0xF3020C),
-        2904 => (0x202EA58, 0x21353D8, 0x2F9FD4C, 0xF3EB8C),
-        2969 => (0x20D68F8, 0x21DD308, 0x3417D4C, 0xFB940C),
-        3073 => (0x20C0DA8, 0x21463B4, 0x33FD0DC, 0xFDEAFC),
-        v if v >= 3272 => (0x20EF128, 0x21F05F8, 0x46E261C, 0x100CB3C),
-        _ => (0, 0, 0, 0),
-    };
-    if !dlls.dll_halo3_odst.is_null() {
-        if odst_levelname != 0 {
-            ptrs.odst_levelname = DeepPtr::new_64bit(dlls.dll_halo3_odst, &[odst_levelname]);
-        }
-        if odst_streets != 0 {
-            ptrs.odst_streets = DeepPtr::new_64bit(dlls.dll_halo3_odst, &[odst_streets]);
-        }
-        if odst_bspstate != 0 {
-            ptrs.odst_bspstate = DeepPtr::new_64bit(dlls.dll_halo3_odst, &[odst_bspstate]);
-        }
-        if odst_deathflag != 0 {
-            ptrs.odst_deathflag = DeepPtr::new_64bit(dlls.dll_halo3_odst, &[odst_deathflag, -0x913i64 as u64]);
-        }
-    }
+    let odst = &offsets.odst;
+    ptrs.odst_levelname = DeepPtr::new_64bit(dlls.dll_halo3_odst, &[odst.levelname]);
+    ptrs.odst_streets = DeepPtr::new_64bit(dlls.dll_halo3_odst, &[odst.streets]);
+    ptrs.odst_bspstate = DeepPtr::new_64bit(dlls.dll_halo3_odst, &[odst.bspstate]);
+    ptrs.odst_deathflag = DeepPtr::new_64bit(dlls.dll_halo3_odst, &[odst.deathflag, -0x913i64 as u64]);
 
     // Halo 4
-    let (h4_levelname, h4_bspstate_offset1, h4_bspstate_offset2) = match v {
-        _v if dlls.dll_halo4.is_null() => (0, 0, 0),
-        2448 => (0x276ACA3, 0x2441AB8, -0x560i64 as u64),
-        2645 => (0x2836433, 0x2472A88, 0),
-        2904 => (0x29A3743, 0x25DC188, 0),
-        2969 => (0x2B03887, 0x27564B0, 0),
-        3073 => (0x2AE485F, 0x2746930, 0),
-        3272 | 3385 => (0x2AFF81F, 0x275D550, 0),
-        v if v >= 3528 => (0x2AFF89F, 0x275D5D0, 0),
-        _ => (0, 0, 0),
+    let h4 = &offsets.h4;
+    ptrs.h4_levelname = DeepPtr::new_64bit(dlls.dll_halo4, &[h4.levelname]);
+    ptrs.h4_bspstate = match h4.bspstate_offset {
+        Some(offset) => DeepPtr::new_64bit(dlls.dll_halo4, &[h4.bspstate, offset]),
+        None => DeepPtr::new_64bit(dlls.dll_halo4, &[h4.bspstate]),
     };
-    if !dlls.dll_halo4.is_null() {
-        if h4_levelname != 0 {
-            ptrs.h4_levelname = DeepPtr::new_64bit(dlls.dll_halo4, &[h4_levelname]);
-        }
-        if h4_bspstate_offset1 != 0 {
-            if h4_bspstate_offset2 != 0 {
-                ptrs.h4_bspstate = DeepPtr::new_64bit(dlls.dll_halo4, &[h4_bspstate_offset1, h4_bspstate_offset2]);
-            } else {
-                ptrs.h4_bspstate = DeepPtr::new_64bit(dlls.dll_halo4, &[h4_bspstate_offset1]);
-            }
-        }
-    }
 }
 
+// Publishes a watcher's current value. Nothing is published while it can't be read, which
+// leaves the last value read showing.
 trait SetTimerVar {
-    fn set_timer_var(&self, name: &str);
+    fn set_timer_var(&self, timer: &mut impl TimerBackend, name: &str);
 }
 
 impl SetTimerVar for Watcher<u8> {
-    fn set_timer_var(&self, name: &str) {
+    fn set_timer_var(&self, timer: &mut impl TimerBackend, name: &str) {
         match &self.pair {
-            Some(pair) => asr::timer::set_variable_int(name, pair.current),
-            None => asr::timer::set_variable(name, ""),
+            Some(pair) => timer.set_variable_int(name, pair.current as u64),
+            None => timer.set_variable(name, ""),
         }
     }
 }
 
 impl SetTimerVar for Watcher<u16> {
-    fn set_timer_var(&self, name: &str) {
+    fn set_timer_var(&self, timer: &mut impl TimerBackend, name: &str) {
         match &self.pair {
-            Some(pair) => asr::timer::set_variable_int(name, pair.current),
-            None => asr::timer::set_variable(name, ""),
+            Some(pair) => timer.set_variable_int(name, pair.current as u64),
+            None => timer.set_variable(name, ""),
         }
     }
 }
 
 impl SetTimerVar for Watcher<u32> {
-    fn set_timer_var(&self, name: &str) {
+    fn set_timer_var(&self, timer: &mut impl TimerBackend, name: &str) {
         match &self.pair {
-            Some(pair) => asr::timer::set_variable_int(name, pair.current),
-            None => asr::timer::set_variable(name, ""),
+            Some(pair) => timer.set_variable_int(name, pair.current as u64),
+            None => timer.set_variable(name, ""),
         }
     }
 }
 
 impl SetTimerVar for Watcher<u64> {
-    fn set_timer_var(&self, name: &str) {
+    fn set_timer_var(&self, timer: &mut impl TimerBackend, name: &str) {
         match &self.pair {
-            Some(pair) => asr::timer::set_variable_int(name, pair.current),
-            None => asr::timer::set_variable(name, ""),
+            Some(pair) => timer.set_variable_int(name, pair.current),
+            None => timer.set_variable(name, ""),
         }
     }
 }
 
 impl SetTimerVar for Watcher<MCCGame> {
-    fn set_timer_var(&self, name: &str) {
+    fn set_timer_var(&self, timer: &mut impl TimerBackend, name: &str) {
         match &self.pair {
-            Some(pair) => asr::timer::set_variable(name, &pair.current.to_string()),
-            None => asr::timer::set_variable(name, ""),
+            Some(pair) => timer.set_variable(name, &pair.current.to_string()),
+            None => timer.set_variable(name, ""),
         }
     }
 }
 
 impl SetTimerVar for Watcher<f32> {
-    fn set_timer_var(&self, name: &str) {
+    fn set_timer_var(&self, timer: &mut impl TimerBackend, name: &str) {
         match &self.pair {
-            Some(pair) => asr::timer::set_variable_float(name, pair.current),
-            None => asr::timer::set_variable(name, ""),
+            Some(pair) => timer.set_variable_float(name, pair.current),
+            None => timer.set_variable(name, ""),
         }
     }
 }
 
 impl SetTimerVar for Watcher<bool> {
-    fn set_timer_var(&self, name: &str) {
+    fn set_timer_var(&self, timer: &mut impl TimerBackend, name: &str) {
         match &self.pair {
-            Some(pair) => asr::timer::set_variable(name, if pair.current { "true" } else { "false" }),
-            None => asr::timer::set_variable(name, ""),
+            Some(pair) => timer.set_variable(name, if pair.current { "true" } else { "false" }),
+            None => timer.set_variable(name, ""),
         }
     }
 }
 
 impl<const N: usize> SetTimerVar for Watcher<ArrayCString<N>> {
-    fn set_timer_var(&self, name: &str) {
+    fn set_timer_var(&self, timer: &mut impl TimerBackend, name: &str) {
         match &self.pair {
-            Some(pair) => asr::timer::set_variable(name, pair.current.validate_utf8().unwrap_or_default()),
-            None => asr::timer::set_variable(name, ""),
+            Some(pair) => timer.set_variable(name, pair.current.validate_utf8().unwrap_or_default()),
+            None => timer.set_variable(name, ""),
         }
     }
 }
 
-fn update_game_state_all(state: &mut GameState, process: &Process, pointers: &GamePointers) {
+fn update_game_state_all(state: &mut GameState, source: &impl MemorySource, pointers: &GamePointers) {
+    state.fadescale = pointers.fadescale;
+    state.h1_checklist = pointers.h1_checklist;
+
     // MCC
-    state.mcc_loadindicator.update(pointers.mcc_loadindicator.deref(&process).ok());
-    state.mcc_menuindicator.update(pointers.mcc_menuindicator.deref(&process).ok());
-    state.mcc_pauseindicator.update(pointers.mcc_pauseindicator.deref(&process).ok());
-    state.mcc_pgcrindicator.update(pointers.mcc_pgcrindicator.deref(&process).ok());
-    state.mcc_gameindicator.update(pointers.mcc_gameindicator.deref(&process).ok());
-    state.mcc_igt_float.update(pointers.mcc_igt_float.deref(&process).ok());
-    state.mcc_comptimerstate.update(pointers.mcc_comptimerstate.deref(&process).ok());
+    read!(state, pointers, source, mcc_loadindicator);
+    read!(state, pointers, source, mcc_menuindicator);
+    read!(state, pointers, source, mcc_pauseindicator);
+    read!(state, pointers, source, mcc_pgcrindicator);
+    read!(state, pointers, source, mcc_gameindicator);
+    read!(state, pointers, source, mcc_igt_float);
+    read!(state, pointers, source, mcc_comptimerstate);
+
+    // Only the active game's DLL is read. Watchers of the game that was played before a switch
+    // are forgotten, so their old values can't fire a start or split once the run comes back.
+    if old!(state.mcc_gameindicator) != current!(state.mcc_gameindicator) {
+        state.clear_inactive_games();
+    }
+
+    match state.current_game() {
+        MCCGame::Halo1 => update_game_state_h1(state, source, pointers),
+        MCCGame::Halo2 => update_game_state_h2(state, source, pointers),
+        MCCGame::Halo3 => update_game_state_h3(state, source, pointers),
+        MCCGame::Halo4 => update_game_state_h4(state, source, pointers),
+        MCCGame::ODST => update_game_state_odst(state, source, pointers),
+        MCCGame::Reach => update_game_state_hr(state, source, pointers),
+        MCCGame::Unknown => {}
+    }
+}
 
-    // Halo 1
-    state.h1_tickcounter.update(pointers.h1_tickcounter.deref(&process).ok());
-    state.h1_igt.update(pointers.h1_igt.deref(&process).ok());
-    state.h1_bspstate.update(pointers.h1_bspstate.deref(&process).ok());
-    state.h1_levelname.update(pointers.h1_levelname.deref(&process).ok());
-    state.h1_gamewon.update(pointers.h1_gamewon.deref(&process).ok());
-    state.h1_cinematic.update(pointers.h1_cinematic.deref(&process).ok());
-    state.h1_cutsceneskip.update(pointers.h1_cutsceneskip.deref(&process).ok());
-    state.h1_xpos.update(pointers.h1_xpos.deref(&process).ok());
-    state.h1_ypos.update(pointers.h1_ypos.deref(&process).ok());
-    state.h1_fadetick.update(pointers.h1_fadetick.deref(&process).ok());
-    state.h1_fadelength.update(pointers.h1_fadelength.deref(&process).ok());
-    state.h1_fadebyte.update(pointers.h1_fadebyte.deref(&process).ok());
-    state.h1_deathflag.update(pointers.h1_deathflag.deref(&process).ok());
-    state.h1_checksum.update(pointers.h1_checksum.deref(&process).ok());
-    state.h1_aflags.update(pointers.h1_aflags.deref(&process).ok());
+fn update_game_state_h1(state: &mut GameState, source: &impl MemorySource, pointers: &GamePointers) {
+    read!(state, pointers, source, h1_tickcounter);
+    read!(state, pointers, source, h1_igt);
+    read!(state, pointers, source, h1_bspstate);
+    read!(state, pointers, source, h1_levelname);
+    read!(state, pointers, source, h1_gamewon);
+    read!(state, pointers, source, h1_cinematic);
+    read!(state, pointers, source, h1_cutsceneskip);
+    read!(state, pointers, source, h1_xpos);
+    read!(state, pointers, source, h1_ypos);
+    read!(state, pointers, source, h1_fadetick);
+    read!(state, pointers, source, h1_fadelength);
+    read!(state, pointers, source, h1_fadebyte);
+    read!(state, pointers, source, h1_deathflag);
+    read!(state, pointers, source, h1_checksum);
+    read!(state, pointers, source, h1_aflags);
+}
 
-    // Halo 2
-    state.h2_levelname.update(pointers.h2_levelname.deref(&process).ok());
-    state.h2_igt.update(pointers.h2_igt.deref(&process).ok());
-    state.h2_bspstate.update(pointers.h2_bspstate.deref(&process).ok());
-    state.h2_deathflag.update(pointers.h2_deathflag.deref(&process).ok());
-    state.h2_tickcounter.update(pointers.h2_tickcounter.deref(&process).ok());
-    state.h2_graphics.update(pointers.h2_graphics.deref(&process).ok());
-    state.h2_fadebyte.update(pointers.h2_fadebyte.deref(&process).ok());
-    state.h2_letterbox.update(pointers.h2_letterbox.deref(&process).ok());
-    state.h2_xpos.update(pointers.h2_xpos.deref(&process).ok());
-    state.h2_ypos.update(pointers.h2_ypos.deref(&process).ok());
-    state.h2_fadetick.update(pointers.h2_fadetick.deref(&process).ok());
-    state.h2_fadelength.update(pointers.h2_fadelength.deref(&process).ok());
+fn update_game_state_h2(state: &mut GameState, source: &impl MemorySource, pointers: &GamePointers) {
+    read!(state, pointers, source, h2_levelname);
+    read!(state, pointers, source, h2_igt);
+    read!(state, pointers, source, h2_bspstate);
+    read!(state, pointers, source, h2_deathflag);
+    read!(state, pointers, source, h2_tickcounter);
+    read!(state, pointers, source, h2_graphics);
+    read!(state, pointers, source, h2_fadebyte);
+    read!(state, pointers, source, h2_letterbox);
+    read!(state, pointers, source, h2_xpos);
+    read!(state, pointers, source, h2_ypos);
+    read!(state, pointers, source, h2_fadetick);
+    read!(state, pointers, source, h2_fadelength);
+}
 
-    // Halo 3
-    state.h3_levelname.update(pointers.h3_levelname.deref(&process).ok());
-    state.h3_theatertime.update(pointers.h3_theatertime.deref(&process).ok());
-    state.h3_tickcounter.update(pointers.h3_tickcounter.deref(&process).ok());
-    state.h3_bspstate.update(pointers.h3_bspstate.deref(&process).ok());
-    state.h3_deathflag.update(pointers.h3_deathflag.deref(&process).ok());
+fn update_game_state_h3(state: &mut GameState, source: &impl MemorySource, pointers: &GamePointers) {
+    read!(state, pointers, source, h3_levelname);
+    read!(state, pointers, source, h3_theatertime);
+    read!(state, pointers, source, h3_tickcounter);
+    read!(state, pointers, source, h3_bspstate);
+    read!(state, pointers, source, h3_deathflag);
+}
 
-    // Halo Reach
-    state.hr_levelname.update(pointers.hr_levelname.deref(&process).ok());
-    state.hr_bspstate.update(pointers.hr_bspstate.deref(&process).ok());
-    state.hr_deathflag.update(pointers.hr_deathflag.deref(&process).ok());
+fn update_game_state_hr(state: &mut GameState, source: &impl MemorySource, pointers: &GamePointers) {
+    read!(state, pointers, source, hr_levelname);
+    read!(state, pointers, source, hr_bspstate);
+    read!(state, pointers, source, hr_deathflag);
+}
 
-    // ODST
-    state.odst_levelname.update(pointers.odst_levelname.deref(&process).ok());
-    state.odst_streets.update(pointers.odst_streets.deref(&process).ok());
-    state.odst_bspstate.update(pointers.odst_bspstate.deref(&process).ok());
-    state.odst_deathflag.update(pointers.odst_deathflag.deref(&process).ok());
+fn update_game_state_odst(state: &mut GameState, source: &impl MemorySource, pointers: &GamePointers) {
+    read!(state, pointers, source, odst_levelname);
+    read!(state, pointers, source, odst_streets);
+    read!(state, pointers, source, odst_bspstate);
+    read!(state, pointers, source, odst_deathflag);
+}
 
-    // Halo 4
-    state.h4_levelname.update(pointers.h4_levelname.deref(&process).ok());
-    state.h4_bspstate.update(pointers.h4_bspstate.deref(&process).ok());
+fn update_game_state_h4(state: &mut GameState, source: &impl MemorySource, pointers: &GamePointers) {
+    read!(state, pointers, source, h4_levelname);
+    read!(state, pointers, source, h4_bspstate);
+}
+
+// Publishes every watcher's value along with how its pointer has been reading, to tell a
+// pointer that broke after a patch from one that's just not in use. A pointer that failed to
+// read keeps its last value showing.
+fn set_debug_timer_vars(state: &GameState, timer: &mut impl TimerBackend) {
+    macro_rules! debug_var {
+        ($state:ident, $timer:ident, $name:ident, $var:literal) => {
+            let health = $state.pointer_health.get(stringify!($name));
+            if !health.is_some_and(|h| h.resolution == Resolution::Failed) {
+                $state.$name.set_timer_var($timer, $var);
+            }
+            set_pointer_health_vars($timer, $var, health);
+        };
+    }
 
     // Debug variables - MCC
-    state.mcc_loadindicator.set_timer_var("MCC Load Indicator");
-    state.mcc_menuindicator.set_timer_var("MCC Menu Indicator");
-    state.mcc_pauseindicator.set_timer_var("MCC Pause Indicator");
-    state.mcc_pgcrindicator.set_timer_var("MCC PGCR Indicator");
-    state.mcc_gameindicator.set_timer_var("MCC Game Indicator");
-    state.mcc_igt_float.set_timer_var("MCC IGT Float");
-    state.mcc_comptimerstate.set_timer_var("MCC Comp Timer State");
+    debug_var!(state, timer, mcc_loadindicator, "MCC Load Indicator");
+    debug_var!(state, timer, mcc_menuindicator, "MCC Menu Indicator");
+    debug_var!(state, timer, mcc_pauseindicator, "MCC Pause Indicator");
+    debug_var!(state, timer, mcc_pgcrindicator, "MCC PGCR Indicator");
+    debug_var!(state, timer, mcc_gameindicator, "MCC Game Indicator");
+    debug_var!(state, timer, mcc_igt_float, "MCC IGT Float");
+    debug_var!(state, timer, mcc_comptimerstate, "MCC Comp Timer State");
 
     // Debug variables - Halo 1
-    state.h1_tickcounter.set_timer_var("H1 Tick Counter");
-    state.h1_igt.set_timer_var("H1 IGT");
-    state.h1_bspstate.set_timer_var("H1 BSP State");
-    state.h1_levelname.set_timer_var("H1 Level Name");
-    state.h1_gamewon.set_timer_var("H1 Game Won");
-    state.h1_cinematic.set_timer_var("H1 Cinematic");
-    state.h1_cutsceneskip.set_timer_var("H1 Cutscene Skip");
-    state.h1_xpos.set_timer_var("H1 X Pos");
-    state.h1_ypos.set_timer_var("H1 Y Pos");
-    state.h1_fadetick.set_timer_var("H1 Fade Tick");
-    state.h1_fadelength.set_timer_var("H1 Fade Length");
-    state.h1_fadebyte.set_timer_var("H1 Fade Byte");
-    state.h1_deathflag.set_timer_var("H1 Death Flag");
-    state.h1_checksum.set_timer_var("H1 Checksum");
-    state.h1_aflags.set_timer_var("H1 A Flags");
+    debug_var!(state, timer, h1_tickcounter, "H1 Tick Counter");
+    debug_var!(state, timer, h1_igt, "H1 IGT");
+    debug_var!(state, timer, h1_bspstate, "H1 BSP State");
+    debug_var!(state, timer, h1_levelname, "H1 Level Name");
+    debug_var!(state, timer, h1_gamewon, "H1 Game Won");
+    debug_var!(state, timer, h1_cinematic, "H1 Cinematic");
+    debug_var!(state, timer, h1_cutsceneskip, "H1 Cutscene Skip");
+    debug_var!(state, timer, h1_xpos, "H1 X Pos");
+    debug_var!(state, timer, h1_ypos, "H1 Y Pos");
+    debug_var!(state, timer, h1_fadetick, "H1 Fade Tick");
+    debug_var!(state, timer, h1_fadelength, "H1 Fade Length");
+    debug_var!(state, timer, h1_fadebyte, "H1 Fade Byte");
+    debug_var!(state, timer, h1_deathflag, "H1 Death Flag");
+    debug_var!(state, timer, h1_checksum, "H1 Checksum");
+    debug_var!(state, timer, h1_aflags, "H1 A Flags");
 
     // Debug variables - Halo 2
-    state.h2_levelname.set_timer_var("H2 Level Name");
-    state.h2_igt.set_timer_var("H2 IGT");
-    state.h2_bspstate.set_timer_var("H2 BSP State");
-    state.h2_deathflag.set_timer_var("H2 Death Flag");
-    state.h2_tickcounter.set_timer_var("H2 Tick Counter");
-    state.h2_graphics.set_timer_var("H2 Graphics");
-    state.h2_fadebyte.set_timer_var("H2 Fade Byte");
-    state.h2_letterbox.set_timer_var("H2 Letterbox");
-    state.h2_xpos.set_timer_var("H2 X Pos");
-    state.h2_ypos.set_timer_var("H2 Y Pos");
-    state.h2_fadetick.set_timer_var("H2 Fade Tick");
-    state.h2_fadelength.set_timer_var("H2 Fade Length");
+    debug_var!(state, timer, h2_levelname, "H2 Level Name");
+    debug_var!(state, timer, h2_igt, "H2 IGT");
+    debug_var!(state, timer, h2_bspstate, "H2 BSP State");
+    debug_var!(state, timer, h2_deathflag, "H2 Death Flag");
+    debug_var!(state, timer, h2_tickcounter, "H2 Tick Counter");
+    debug_var!(state, timer, h2_graphics, "H2 Graphics");
+    debug_var!(state, timer, h2_fadebyte, "H2 Fade Byte");
+    debug_var!(state, timer, h2_letterbox, "H2 Letterbox");
+    debug_var!(state, timer, h2_xpos, "H2 X Pos");
+    debug_var!(state, timer, h2_ypos, "H2 Y Pos");
+    debug_var!(state, timer, h2_fadetick, "H2 Fade Tick");
+    debug_var!(state, timer, h2_fadelength, "H2 Fade Length");
 
     // Debug variables - Halo 3
-    state.h3_levelname.set_timer_var("H3 Level Name");
-    state.h3_theatertime.set_timer_var("H3 Theater Time");
-    state.h3_tickcounter.set_timer_var("H3 Tick Counter");
-    state.h3_bspstate.set_timer_var("H3 BSP State");
-    state.h3_deathflag.set_timer_var("H3 Death Flag");
+    debug_var!(state, timer, h3_levelname, "H3 Level Name");
+    debug_var!(state, timer, h3_theatertime, "H3 Theater Time");
+    debug_var!(state, timer, h3_tickcounter, "H3 Tick Counter");
+    debug_var!(state, timer, h3_bspstate, "H3 BSP State");
+    debug_var!(state, timer, h3_deathflag, "H3 Death Flag");
 
     // Debug variables - Halo Reach
-    state.hr_levelname.set_timer_var("HR Level Name");
-    state.hr_bspstate.set_timer_var("HR BSP State");
-    state.hr_deathflag.set_timer_var("HR Death Flag");
+    debug_var!(state, timer, hr_levelname, "HR Level Name");
+    debug_var!(state, timer, hr_bspstate, "HR BSP State");
+    debug_var!(state, timer, hr_deathflag, "HR Death Flag");
 
     // Debug variables - ODST
-    state.odst_levelname.set_timer_var("ODST Level Name");
-    state.odst_streets.set_timer_var("ODST Streets");
-    state.odst_bspstate.set_timer_var("ODST BSP State");
-    state.odst_deathflag.set_timer_var("ODST Death Flag");
+    debug_var!(state, timer, odst_levelname, "ODST Level Name");
+    debug_var!(state, timer, odst_streets, "ODST Streets");
+    debug_var!(state, timer, odst_bspstate, "ODST BSP State");
+    debug_var!(state, timer, odst_deathflag, "ODST Death Flag");
 
     // Debug variables - Halo 4
-    state.h4_levelname.set_timer_var("H4 Level Name");
-    state.h4_bspstate.set_timer_var("H4 BSP State");
+    debug_var!(state, timer, h4_levelname, "H4 Level Name");
+    debug_var!(state, timer, h4_bspstate, "H4 BSP State");
 }
 
+fn set_pointer_health_vars(timer: &mut impl TimerBackend, var: &str, health: Option<&PointerHealth>) {
+    let status = match health.map(|h| h.resolution) {
+        Some(Resolution::Resolved(())) => "Resolved",
+        Some(Resolution::Missing) => "Missing",
+        Some(Resolution::Failed) => "Failed",
+        None => "Not read",
+    };
+    timer.set_variable(&format!("{} Pointer", var), status);
+    timer.set_variable_int(&format!("{} Failed Ticks", var), health.map_or(0, |h| h.failed_ticks) as u64);
+}
+
+#[cfg_attr(not(target_family = "wasm"), allow(dead_code))]
 async fn main() {
     let mut settings = Settings::register();
     let mut state = GameState::default();
     let mut splitter = SplitterState::default();
+    let mut timer = AsrTimer::new();
 
     loop {
         let exe_names = ["MCC-Win64-Shipping.exe", "MCC-Win64-Shipping-WinStore.exe", "MCCWinStore-Win64-Shipping.exe"];
@@ -1002,6 +1404,7 @@ async fn main() {
         let Some(process) = exe_names.into_iter().find_map(|name| Process::attach(name)) else {
 
             settings.update();
+            set_tick_rate(&mut splitter, &mut timer, MENU_TICK_RATE);
 
             if settings.loop_mode && settings.level_mode != LevelMode::IndividualLevel {
                 let settingscopy = asr::settings::Map::load();
@@ -1036,8 +1439,9 @@ async fn main() {
             mcc_version.major_version, mcc_version.minor_version, mcc_version.build_part, mcc_version.private_part
         );
 
-        asr::timer::set_variable("MCC Version", mcc_version_str);
-        asr::timer::set_variable(
+        timer.set_variable("MCC Version", mcc_version_str);
+        state.mcc_version = mcc_version_str.clone();
+        timer.set_variable(
             "Is WinStore",
             match is_winstore {
                 true => "true",
@@ -1045,21 +1449,23 @@ async fn main() {
             },
         );
 
-        // WinStore is unsupported below version 3272
-        if is_winstore && mcc_version.minor_version < 3272 {
+        // WinStore is unsupported on versions without WinStore offsets
+        if is_winstore && offsets::closest(mcc_version.minor_version).and_then(|o| o.mcc(true)).is_none() {
             // We don't want to burn CPU by constantly attaching/detaching so
             // spin in an idle loop until the game is closed.
-            print_message(&format!("WinStore version {} is not supported, going into idle mode.", mcc_version_str));
+            set_splitter_status(&mut splitter, &mut timer, &format!("Unsupported MCC version {} (WinStore)", mcc_version_str));
+            set_tick_rate(&mut splitter, &mut timer, MENU_TICK_RATE);
             process.until_closes(asr::future::next_tick()).await;
             continue;
         }
 
         process
             .until_closes(async {
-                let mut dlls = GameDLLs::default();
+                let mut known_dlls: Option<GameDLLs> = None;
+                let mut module_scan = 0;
                 let mut ptrs = GamePointers::default();
-
-                dlls.exe_mcc = mcc_addr;
+                let mut trace: Option<TraceRecorder> = None;
+                let mut scanned = ScannedOffsets::default();
 
                 loop {
                     asr::future::next_tick().await;
@@ -1073,79 +1479,228 @@ async fn main() {
                         //settings.loop_mode = false;
                     }
 
+                    // Game DLLs only move when a game loads or unloads, so look them up every so
+                    // often or when the game switches, and rebuild the pointers when one moved.
+                    // The DLL can finish loading after the switch, so until the current game's
+                    // DLL is found it's looked up every tick.
+                    let dll_missing = known_dlls.is_some_and(|dlls| !dlls.is_loaded(state.current_game()));
+                    if module_scan == 0 || dll_missing || changed!(state.mcc_gameindicator) {
+                        module_scan = MODULE_SCAN_TICKS;
+                        let dlls = GameDLLs::find(&process, mcc_addr);
+                        if known_dlls != Some(dlls) {
+                            dlls.set_timer_vars(known_dlls.as_ref(), &mut timer);
+
+                            if !offsets::is_known(mcc_version.minor_version) && scanned.update(&process, exe_name, &dlls) {
+                                if scanned.is_empty() {
+                                    print_message(&format!("Unknown MCC version {}, no globals found by signature.", mcc_version_str));
+                                } else {
+                                    print_message(&format!("Unknown MCC version {}, found {} globals by signature.", mcc_version_str, scanned.len()));
+                                }
+                            }
 
+                            update_game_pointers(is_winstore, mcc_version, &dlls, &scanned, &mut ptrs);
+                            known_dlls = Some(dlls);
+                        }
+                    }
+                    module_scan -= 1;
 
-                    dlls.dll_halo1 = process.get_module_address("halo1.dll").unwrap_or_default();
-                    dlls.dll_halo2 = process.get_module_address("halo2.dll").unwrap_or_default();
-                    dlls.dll_halo3 = process.get_module_address("halo3.dll").unwrap_or_default();
-                    dlls.dll_halo4 = process.get_module_address("halo4.dll").unwrap_or_default();
-                    dlls.dll_halo3_odst = process.get_module_address("halo3odst.dll").unwrap_or_default();
-                    dlls.dll_halo_reach = process.get_module_address("haloreach.dll").unwrap_or_default();
+                    update_game_state_all(&mut state, &process, &ptrs);
+                    if settings.pointer_diagnostics {
+                        set_debug_timer_vars(&state, &mut timer);
+                    }
 
-                    asr::timer::set_variable("dll_h1", &dlls.dll_halo1.to_string());
-                    asr::timer::set_variable("dll_h2", &dlls.dll_halo2.to_string());
-                    asr::timer::set_variable("dll_h3", &dlls.dll_halo3.to_string());
-                    asr::timer::set_variable("dll_h4", &dlls.dll_halo4.to_string());
-                    asr::timer::set_variable("dll_h3_odst", &dlls.dll_halo3_odst.to_string());
-                    asr::timer::set_variable("dll_reach", &dlls.dll_halo_reach.to_string());
+                    if !settings.record_trace {
+                        trace = None;
+                    } else if trace.is_none() {
+                        print_message(&TraceRecorder::header(mcc_version_str, is_winstore));
+                        print_message(&settings.trace_line());
+                        trace = Some(TraceRecorder::default());
+                    }
+                    if let Some(recorder) = trace.as_mut() {
+                        recorder.begin_tick();
+                        state.visit_watchers(recorder);
+                        if let Some(line) = recorder.finish_tick() {
+                            print_message(line);
+                        }
+                    }
 
-                    update_game_pointers(is_winstore, mcc_version, &dlls, &mut ptrs);
+                    update_timer(&mut state, &settings, &mut splitter, &mut timer);
+                }
+            })
+            .await;
+    }
+}
 
-                    update_game_state_all(&mut state, &process, &ptrs);
+// Replays a trace recorded with the "Record watcher trace" setting through the splitter logic,
+// using the settings stored in the trace. The timer records every command issued per tick.
+pub fn replay_trace(trace: &str, timer: &mut RecordingTimer) -> Result<(), TraceError> {
+    let mut reader = TraceReader::new(trace)?;
 
-                    // Get current game
-                    let Some(current_game_u8) = current!(state.mcc_gameindicator) else { continue };
-                    let Some(menu_indicator) = current!(state.mcc_menuindicator) else { continue };
-                    let Some(load_indicator) = current!(state.mcc_loadindicator) else { continue };
+    let mut settings = Settings::default();
+    for (name, value) in reader.settings.iter() {
+        settings.apply_trace_option(name, value);
+    }
 
-                    let current_game = MCCGame::from(current_game_u8);
+    let mut state = GameState::default();
+    let mut splitter = SplitterState::default();
 
-                    update_splitter_state(&mut state, &settings, &mut splitter, current_game, menu_indicator);
+    // Version is stored as major.minor.build.private
+    let minor_version = reader.mcc_version.split('.').nth(1).and_then(|v| v.parse().ok()).unwrap_or_default();
+    state.mcc_version = reader.mcc_version.to_string();
+    if let Some(offsets) = offsets::closest(minor_version) {
+        state.fadescale = offsets.fadescale;
+        state.h1_checklist = offsets.h1.checklist;
+    }
 
-                    // Split/Reset depending on timer state
-                    match asr::timer::state() {
-                        TimerState::NotRunning => {
-                            if splitter.vars_reset {
-                                splitter = SplitterState::default();
-                                splitter.vars_reset = false;
-                            }
+    while let Some((tick, mut applier)) = reader.next_tick()? {
+        state.visit_watchers(&mut applier);
+        applier.finish()?;
 
-                            // Check for start conditions
-                            if should_start(&state, &settings, &mut splitter, current_game, menu_indicator) {
-                                asr::timer::start();
-                            }
-                        }
-                        TimerState::Running | TimerState::Paused => {
-                            if !splitter.vars_reset {
-                                splitter.vars_reset = true;
-                            }
+        timer.tick = tick;
+        update_timer(&mut state, &settings, &mut splitter, timer);
+    }
 
-                            // Check for reset
-                            if should_reset(&state, &settings, &splitter, current_game, menu_indicator) {
-                                asr::timer::reset();
-                                splitter.reset();
-                                continue;
-                            }
+    Ok(())
+}
 
-                            // Check for split
-                            if should_split(&state, &settings, &mut splitter, current_game, menu_indicator) {
-                                asr::timer::split();
-                            }
+// Runs the start/split/reset/load removal logic for one tick of already updated game state.
+fn update_timer(state: &mut GameState, settings: &Settings, splitter: &mut SplitterState, timer: &mut impl TimerBackend) {
+    let supported = update_splitter_status(state, splitter, timer);
+    update_tick_rate(state, splitter, timer, supported);
+    if !supported {
+        return;
+    }
 
-                            // Handle loading/game time
-                            handle_loading(&state, &settings, &mut splitter, current_game, menu_indicator, load_indicator);
+    // Get current game
+    let Some(current_game_u8) = current!(state.mcc_gameindicator) else { return };
+    let Some(menu_indicator) = current!(state.mcc_menuindicator) else { return };
+    let Some(load_indicator) = current!(state.mcc_loadindicator) else { return };
 
-                            // Update death counter
-                            update_death_counter(&state, &mut splitter, current_game);
-                        }
-                        TimerState::Ended => {
-                            // Timer has ended, wait for reset
-                        }
-                        _ => {}
-                    }
-                }
-            })
-            .await;
+    let current_game = MCCGame::from(current_game_u8);
+
+    update_splitter_state(state, settings, splitter, current_game, menu_indicator);
+    update_h1_map_check(state, splitter, timer, current_game);
+    update_game_vars(state, splitter, timer, current_game);
+    update_level_vars(state, splitter, timer, current_game);
+    update_route_var(settings, splitter, timer);
+
+    // Split/Reset depending on timer state
+    match timer.state() {
+        TimerState::NotRunning => {
+            if splitter.vars_reset {
+                *splitter = SplitterState::default();
+                splitter.vars_reset = false;
+            }
+
+            // Check for start conditions
+            if should_start(state, settings, splitter, current_game, menu_indicator) {
+                timer.start();
+            }
+        }
+        TimerState::Running | TimerState::Paused => {
+            if !splitter.vars_reset {
+                splitter.vars_reset = true;
+            }
+
+            // Check for reset
+            if should_reset(state, settings, splitter, current_game, menu_indicator, timer.state()) {
+                timer.reset();
+                splitter.reset();
+                return;
+            }
+
+            // Check for a level loaded from the main menu
+            update_sq_split(state, settings, splitter, current_game, menu_indicator);
+
+            // Check for split
+            if should_split(state, settings, splitter, current_game, menu_indicator) {
+                timer.split();
+            }
+
+            // Handle loading/game time
+            handle_loading(state, settings, splitter, timer, current_game, menu_indicator, load_indicator);
+
+            // Update death counter
+            update_death_counter(state, splitter, timer, current_game);
+
+            // Check the game isn't running fast
+            update_h1_speed_check(state, splitter, timer, current_game);
+        }
+        TimerState::Ended => {
+            // Timer has ended, wait for reset
+        }
+        _ => {}
+    }
+}
+
+// Reports whether the pointers of MCC and the current game could be read, and returns false
+// if the build has no offsets for them, so the game's rules are left alone rather than run
+// on values that can't be read.
+fn update_splitter_status(state: &GameState, splitter: &mut SplitterState, timer: &mut impl TimerBackend) -> bool {
+    // Every pointer read fine, which is the case on almost every tick
+    if state.pointer_health.values().all(|h| h.resolution == Resolution::Resolved(())) {
+        set_splitter_status(splitter, timer, "OK");
+        return true;
+    }
+
+    let game = state.current_game();
+    let missing = state.unresolved(game, Resolution::Missing);
+    let failed = state.unresolved(game, Resolution::Failed);
+    let status = if missing.iter().any(|name| name.starts_with("mcc_")) {
+        format!("Unsupported MCC version {}", state.mcc_version)
+    } else if !missing.is_empty() {
+        format!("{} not supported on this MCC version, missing {}", game, missing.join(", "))
+    } else if !failed.is_empty() {
+        format!("Couldn't read {}", failed.join(", "))
+    } else {
+        "OK".to_string()
+    };
+    set_splitter_status(splitter, timer, &status);
+    missing.is_empty()
+}
+
+fn set_splitter_status(splitter: &mut SplitterState, timer: &mut impl TimerBackend, status: &str) {
+    if splitter.status_var.as_deref() == Some(status) {
+        return;
+    }
+    if splitter.status_var.is_some() || status != "OK" {
+        print_message(&format!("Splitter status: {}", status));
+    }
+    timer.set_variable("Splitter Status", status);
+    splitter.status_var = Some(status.to_string());
+}
+
+// Ticks per second while sitting in the main menu or detached, where nothing can happen
+// until a load starts.
+const MENU_TICK_RATE: f64 = 10.0;
+// Ticks per second everywhere else. Twice the rate of 60 tick games, so windows a single game
+// tick long, like the tick counter check in Halo 2's start, are never stepped over.
+const GAMEPLAY_TICK_RATE: f64 = 120.0;
+
+// Polls slowly in the main menu. Leaving it always goes through a load, which brings the
+// rate back up before the level starts ticking, and the PGCR and the loads after it are all
+// reached from gameplay. The pause menu also sets the menu indicator, but keeps the level
+// loaded, so the rate only drops once no map is loaded. Nothing can be timed while the
+// splitter is unsupported or MCC's state can't be read, so that polls slowly too.
+fn update_tick_rate(state: &GameState, splitter: &mut SplitterState, timer: &mut impl TimerBackend, supported: bool) {
+    let menu_indicator = current!(state.mcc_menuindicator);
+    let load_indicator = current!(state.mcc_loadindicator);
+    let in_menu = match (menu_indicator, load_indicator) {
+        (Some(0), Some(0)) => map_name(state, state.current_game()).is_none_or(|name| name.is_empty()),
+        (Some(_), Some(_)) => false,
+        _ => true,
+    };
+    let rate = match !supported || in_menu {
+        true => MENU_TICK_RATE,
+        false => GAMEPLAY_TICK_RATE,
+    };
+    set_tick_rate(splitter, timer, rate);
+}
+
+fn set_tick_rate(splitter: &mut SplitterState, timer: &mut impl TimerBackend, rate: f64) {
+    if splitter.tick_rate != rate {
+        splitter.tick_rate = rate;
+        timer.set_tick_rate(rate);
     }
 }
 
@@ -1154,9 +1709,7 @@ fn update_splitter_state(state: &mut GameState, settings: &Settings, splitter: &
         if splitter.h3_reset_flag || settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
             splitter.h3_reset_flag = false;
         }
-        if splitter.pgcr_exists {
-            splitter.pgcr_exists = false;
-        }
+        splitter.igt.clear_pgcr();
     }
 
     if current_game == MCCGame::Halo2 && menu_indicator == 1 {
@@ -1169,11 +1722,11 @@ fn update_splitter_state(state: &mut GameState, settings: &Settings, splitter: &
 
 fn update_h2_tgj_flag(state: &GameState, splitter: &mut SplitterState) {
     let Some(level) = current!(state.h2_levelname) else { return };
-    let Some(level_str) = level.validate_utf8().ok() else { return };
+    let level = H2Level::parse(level.as_bytes());
     let Some(bspstate) = current!(state.h2_bspstate) else { return };
     let Some(tickcounter) = current!(state.h2_tickcounter) else { return };
 
-    if level_str == "08b" && !splitter.h2_tgj_ready_flag {
+    if level == Some(H2Level::GreatJourney) && !splitter.h2_tgj_ready_flag {
         if bspstate == 3 {
             splitter.h2_tgj_ready_flag = true;
             splitter.h2_tgj_ready_time = tickcounter;
@@ -1182,19 +1735,95 @@ fn update_h2_tgj_flag(state: &GameState, splitter: &mut SplitterState) {
 
     // Reset flag on level change
     let Some(level_old) = old!(state.h2_levelname) else { return };
-    let Some(level_str_old) = level_old.validate_utf8().ok() else { return };
-    if level_str != level_str_old {
+    if level != H2Level::parse(level_old.as_bytes()) {
         splitter.h2_tgj_ready_flag = false;
         splitter.h2_tgj_ready_time = 0;
     }
 }
 
+// Sets force_split2 when a different level is loaded from the main menu. The menu indicator
+// goes 0 -> 1 on the load but the level name can change a few ticks later, so the split waits
+// for the name to differ from the last level played. A load started in game means the menu
+// brought back the same level, so it stops waiting and leaves that load to the usual split.
+fn update_sq_split(state: &GameState, settings: &Settings, splitter: &mut SplitterState, current_game: MCCGame, menu_indicator: u8) {
+    if !settings.sq_split {
+        return;
+    }
+
+    let level = match current_game {
+        MCCGame::Halo1 | MCCGame::Halo2 => current_level(state, current_game),
+        _ => None,
+    };
+    let Some(level) = level else { return };
+    let Some(menu_indicator_old) = old!(state.mcc_menuindicator) else { return };
+    let Some(load_indicator) = current!(state.mcc_loadindicator) else { return };
+    let Some(load_indicator_old) = old!(state.mcc_loadindicator) else { return };
+
+    if menu_indicator == 0 {
+        splitter.sq_pending = false;
+        return;
+    }
+
+    if menu_indicator_old == 1 && load_indicator == 1 && load_indicator_old == 0 {
+        splitter.sq_pending = false;
+    }
+
+    if menu_indicator_old == 0 {
+        splitter.sq_pending = true;
+    }
+
+    if splitter.sq_pending {
+        if splitter.sq_level == Some(level) {
+            return;
+        }
+        splitter.sq_pending = false;
+        splitter.force_split2 = true;
+    }
+
+    splitter.sq_level = Some(level);
+}
+
+// Compares the loaded H1 map against the stock checksum for this version. None for maps that
+// aren't part of the base game, unknown versions and while the map is loading.
+fn check_h1_map(state: &GameState) -> Option<bool> {
+    let level = level!(state.h1_levelname, H1Level)?;
+    let expected = state.h1_checklist.get(level);
+    let checksum = current!(state.h1_checksum)?;
+    let load_indicator = current!(state.mcc_loadindicator)?;
+
+    if expected == 0 || checksum == 0 || load_indicator != 0 {
+        return None;
+    }
+    Some(checksum == expected)
+}
+
+fn update_h1_map_check(state: &GameState, splitter: &mut SplitterState, timer: &mut impl TimerBackend, current_game: MCCGame) {
+    splitter.h1_map_valid = if current_game == MCCGame::Halo1 { check_h1_map(state) } else { None };
+
+    let running = matches!(timer.state(), TimerState::Running | TimerState::Paused);
+    if running && splitter.h1_map_valid == Some(false) && splitter.h1_altered_map.is_none() {
+        if let Some(Level::Halo1(level)) = current_level(state, current_game) {
+            print_message(&format!("Halo: CE map {} doesn't match the stock checksum, run flagged.", level.code()));
+            splitter.h1_altered_map = Some(level);
+        }
+    }
+
+    let map_valid = match (splitter.h1_altered_map.is_some(), splitter.h1_map_valid) {
+        (true, _) | (false, Some(false)) => "No",
+        (false, Some(true)) => "Yes",
+        (false, None) => "Unknown",
+    };
+    if map_valid != splitter.map_valid_var {
+        splitter.map_valid_var = map_valid;
+        timer.set_variable("Map Valid", map_valid);
+    }
+}
+
 fn update_h3_reset_flag(state: &GameState, splitter: &mut SplitterState) {
     let Some(level) = current!(state.h3_levelname) else { return };
-    let Some(level_str) = level.validate_utf8().ok() else { return };
     let Some(theatertime) = current!(state.h3_theatertime) else { return };
 
-    if level_str == "010" && theatertime >= 15 {
+    if H3Level::parse(level.as_bytes()) == Some(H3Level::FIRST) && theatertime >= 15 {
         splitter.h3_reset_flag = true;
     }
 }
@@ -1206,7 +1835,7 @@ fn should_start(state: &GameState, settings: &Settings, splitter: &mut SplitterS
 
     splitter.started_game = current_game;
 
-    match current_game {
+    let start = match current_game {
         MCCGame::Halo1 => should_start_h1(state, settings, splitter).unwrap_or(false),
         MCCGame::Halo2 => should_start_h2(state, settings, splitter).unwrap_or(false),
         MCCGame::Halo3 => should_start_h3(state, settings, splitter).unwrap_or(false),
@@ -1214,14 +1843,29 @@ fn should_start(state: &GameState, settings: &Settings, splitter: &mut SplitterS
         MCCGame::ODST => should_start_odst(state, settings, splitter).unwrap_or(false),
         MCCGame::Reach => should_start_hr(state, settings, splitter).unwrap_or(false),
         _ => false,
+    };
+
+    // A multigame route only starts on its first game
+    if start && settings.route().is_some_and(|route| route[0] != current_game) {
+        splitter.route_out_of_order = Some(current_game);
+        return false;
     }
+    if start {
+        splitter.started_map = map_name(state, current_game).unwrap_or_default().to_string();
+        splitter.route_game = 0;
+        splitter.route_out_of_order = None;
+    }
+    start
 }
 
 fn should_start_h1(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.h1_levelname)?;
-    let level_str = level.validate_utf8().ok()?;
+    let name = current!(state.h1_levelname)?;
+    if name.is_empty() {
+        return Some(false);
+    }
+    let level = H1Level::parse(name.as_bytes());
 
-    if level_str.is_empty() {
+    if settings.h1_map_check && splitter.h1_map_valid == Some(false) {
         return Some(false);
     }
 
@@ -1234,57 +1878,51 @@ fn should_start_h1(state: &GameState, settings: &Settings, splitter: &mut Splitt
     let cutsceneskip_old = old!(state.h1_cutsceneskip)?;
 
     // Check IL start conditions
-    let should_start = match level_str {
-        "a10" => {
-            if settings.level_mode == LevelMode::IndividualLevel || settings.any_level || level_str == "a10" {
-                bspstate == 0 && xpos < -55.0 && tickcounter > 280 && !cinematic && cinematic_old
-            } else {
-                false
-            }
-        }
-        "a30" => {
+    let should_start = match level {
+        Some(H1Level::FIRST) => bspstate == 0 && xpos < -55.0 && tickcounter > 280 && !cinematic && cinematic_old,
+        Some(H1Level::Halo) => {
             if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
                 ((tickcounter >= 182 && tickcounter < 190) || (!cinematic && cinematic_old && tickcounter > 500 && tickcounter < 900)) && !cutsceneskip
             } else {
                 false
             }
         }
-        "a50" => {
+        Some(H1Level::TruthAndReconciliation) => {
             if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
                 tickcounter > 30 && tickcounter < 900 && !cinematic && cinematic_old
             } else {
                 false
             }
         }
-        "b30" => {
+        Some(H1Level::SilentCartographer) => {
             if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
                 tickcounter > 30 && tickcounter < 1060 && !cinematic && cinematic_old
             } else {
                 false
             }
         }
-        "b40" => {
+        Some(H1Level::AssaultOnTheControlRoom) => {
             if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
                 tickcounter > 30 && tickcounter < 950 && !cinematic && cinematic_old
             } else {
                 false
             }
         }
-        "c10" => {
+        Some(H1Level::GuiltySpark) => {
             if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
                 tickcounter > 30 && tickcounter < 700 && !cinematic && cinematic_old
             } else {
                 false
             }
         }
-        "c20" | "c40" | "d20" | "d40" => {
+        Some(H1Level::Library | H1Level::TwoBetrayals | H1Level::Keyes | H1Level::Maw) => {
             if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
                 !cutsceneskip && cutsceneskip_old
             } else {
                 false
             }
         }
-        _ => {
+        None => {
             if settings.any_start {
                 (!cutsceneskip && cutsceneskip_old) || (tickcounter > 30 && !cinematic && cinematic_old)
             } else {
@@ -1294,7 +1932,7 @@ fn should_start_h1(state: &GameState, settings: &Settings, splitter: &mut Splitt
     };
 
     if should_start {
-        splitter.started_level = level_str.to_string();
+        splitter.started_level = level.map(Level::from);
         Some(true)
     } else {
         Some(false)
@@ -1302,9 +1940,7 @@ fn should_start_h1(state: &GameState, settings: &Settings, splitter: &mut Splitt
 }
 
 fn should_start_h2(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.h2_levelname)?;
-    let level_str = level.validate_utf8().ok()?;
-
+    let level = level!(state.h2_levelname, H2Level);
     let tickcounter = current!(state.h2_tickcounter)?;
     let fadebyte = current!(state.h2_fadebyte)?;
     let fadebyte_old = old!(state.h2_fadebyte)?;
@@ -1312,31 +1948,31 @@ fn should_start_h2(state: &GameState, settings: &Settings, splitter: &mut Splitt
     let load_indicator = current!(state.mcc_loadindicator)?;
     let bspstate = current!(state.h2_bspstate)?;
 
-    if settings.level_mode == LevelMode::IndividualLevel && level_str != "01a" {
+    if settings.level_mode == LevelMode::IndividualLevel && level != Some(H2Level::FIRST) {
         if igt > 10 && igt < 30 {
-            splitter.started_level = level_str.to_string();
+            splitter.started_level = level.map(Level::from);
             return Some(true);
         }
     } else {
-        if level_str == "01a" && tickcounter >= 26 && tickcounter < 30 {
-            splitter.started_level = level_str.to_string();
+        if level == Some(H2Level::FIRST) && tickcounter >= 26 && tickcounter < 30 {
+            splitter.started_level = level.map(Level::from);
             return Some(true);
-        } else if level_str == "01b" && load_indicator == 0 && fadebyte == 0 && fadebyte_old == 1 && tickcounter < 30 {
-            splitter.started_level = level_str.to_string();
+        } else if level == Some(H2Level::Cairo) && load_indicator == 0 && fadebyte == 0 && fadebyte_old == 1 && tickcounter < 30 {
+            splitter.started_level = level.map(Level::from);
             return Some(true);
         } else if (settings.any_level || settings.level_mode == LevelMode::IndividualLevel) && load_indicator == 0 {
-            if level_str == "03a" {
+            if level == Some(H2Level::Outskirts) {
                 // Outskirts special logic
                 let fadetick = current!(state.h2_fadetick)?;
                 let fadelength = current!(state.h2_fadelength)?;
                 if fadebyte == 1 && bspstate == 0 && tickcounter > 10 && tickcounter < 100 {
                     if fadelength > 15 && tickcounter >= fadetick + (fadelength as f64 * 0.067) as u32 {
-                        splitter.started_level = level_str.to_string();
+                        splitter.started_level = level.map(Level::from);
                         return Some(true);
                     }
                 }
             } else if fadebyte == 0 && fadebyte_old == 1 && tickcounter < 120 {
-                splitter.started_level = level_str.to_string();
+                splitter.started_level = level.map(Level::from);
                 return Some(true);
             }
         }
@@ -1346,9 +1982,7 @@ fn should_start_h2(state: &GameState, settings: &Settings, splitter: &mut Splitt
 }
 
 fn should_start_h3(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.h3_levelname)?;
-    let level_str = level.validate_utf8().ok()?;
-
+    let level = level!(state.h3_levelname, H3Level);
     let igt_float = current!(state.mcc_igt_float)?;
     let theatertime = current!(state.h3_theatertime)?;
     let tickcounter = current!(state.h3_tickcounter)?;
@@ -1357,15 +1991,15 @@ fn should_start_h3(state: &GameState, settings: &Settings, splitter: &mut Splitt
 
     if settings.level_mode == LevelMode::IndividualLevel {
         if igt_float > 0.167 && igt_float < 0.5 {
-            splitter.started_level = level_str.to_string();
+            splitter.started_level = level.map(Level::from);
             return Some(true);
         }
-    } else if settings.any_level || level_str == "010" {
+    } else if settings.any_level || level == Some(H3Level::FIRST) {
         if load_indicator == 0 && theatertime > 15 && theatertime < 30 {
-            splitter.started_level = level_str.to_string();
+            splitter.started_level = level.map(Level::from);
             return Some(true);
-        } else if splitter.h3_reset_flag && level_str == "010" && tickcounter > 0 && tickcounter < 15 && tickcounter > tickcounter_old {
-            splitter.started_level = level_str.to_string();
+        } else if splitter.h3_reset_flag && level == Some(H3Level::FIRST) && tickcounter > 0 && tickcounter < 15 && tickcounter > tickcounter_old {
+            splitter.started_level = level.map(Level::from);
             return Some(true);
         }
     }
@@ -1374,13 +2008,11 @@ fn should_start_h3(state: &GameState, settings: &Settings, splitter: &mut Splitt
 }
 
 fn should_start_h4(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.h4_levelname)?;
-    let level_str = level.validate_utf8().ok()?;
-
+    let level = level!(state.h4_levelname, H4Level);
     let igt_float = current!(state.mcc_igt_float)?;
 
-    if (settings.level_mode == LevelMode::IndividualLevel || settings.any_level || level_str == "m10") && igt_float > 0.167 && igt_float < 0.5 {
-        splitter.started_level = level_str.to_string();
+    if (settings.level_mode == LevelMode::IndividualLevel || settings.any_level || level == Some(H4Level::FIRST)) && igt_float > 0.167 && igt_float < 0.5 {
+        splitter.started_level = level.map(Level::from);
         return Some(true);
     }
 
@@ -1388,13 +2020,15 @@ fn should_start_h4(state: &GameState, settings: &Settings, splitter: &mut Splitt
 }
 
 fn should_start_odst(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.odst_levelname)?;
-    let level_str = level.validate_utf8().ok()?;
+    let level = level!(state.odst_levelname, OdstLevel);
     let streets = current!(state.odst_streets)?;
     let igt_float = current!(state.mcc_igt_float)?;
 
-    if (settings.level_mode == LevelMode::IndividualLevel || settings.any_level || (level_str == "h100" && streets == 0)) && igt_float > 0.167 && igt_float < 0.5 {
-        splitter.started_level = level_str.to_string();
+    if (settings.level_mode == LevelMode::IndividualLevel || settings.any_level || (level == Some(OdstLevel::FIRST) && streets == 0))
+        && igt_float > 0.167
+        && igt_float < 0.5
+    {
+        splitter.started_level = level.map(Level::from);
         splitter.started_scene = streets;
         return Some(true);
     }
@@ -1403,25 +2037,27 @@ fn should_start_odst(state: &GameState, settings: &Settings, splitter: &mut Spli
 }
 
 fn should_start_hr(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.hr_levelname)?;
-    let level_str = level.validate_utf8().ok()?;
+    let level = level!(state.hr_levelname, HrLevel);
     let igt_float = current!(state.mcc_igt_float)?;
 
-    if (settings.level_mode == LevelMode::IndividualLevel || settings.any_level || level_str == "m10") && igt_float > 0.167 && igt_float < 0.5 {
-        splitter.started_level = level_str.to_string();
+    if (settings.level_mode == LevelMode::IndividualLevel || settings.any_level || level == Some(HrLevel::FIRST))
+        && igt_float > 0.167
+        && igt_float < 0.5
+    {
+        splitter.started_level = level.map(Level::from);
         return Some(true);
     }
 
     Some(false)
 }
 
-fn should_reset(state: &GameState, settings: &Settings, splitter: &SplitterState, current_game: MCCGame, menu_indicator: u8) -> bool {
+fn should_reset(state: &GameState, settings: &Settings, splitter: &SplitterState, current_game: MCCGame, menu_indicator: u8, timer_state: TimerState) -> bool {
     if settings.level_mode == LevelMode::IndividualLevel && settings.loop_mode {
         return false;
     }
 
     // Reset on main menu in IL mode
-    if settings.level_mode == LevelMode::IndividualLevel && menu_indicator == 0 && asr::timer::state() != TimerState::Ended {
+    if settings.level_mode == LevelMode::IndividualLevel && menu_indicator == 0 && timer_state != TimerState::Ended {
         return true;
     }
 
@@ -1445,8 +2081,7 @@ fn should_reset_h1(state: &GameState, settings: &Settings, splitter: &SplitterSt
         return Some(false);
     }
 
-    let level = current!(state.h1_levelname)?;
-    let level_str = level.validate_utf8().ok()?;
+    let level = level!(state.h1_levelname, H1Level).map(Level::from);
     let igt = current!(state.h1_igt)?;
     let igt_old = old!(state.h1_igt)?;
     let tickcounter = current!(state.h1_tickcounter)?;
@@ -1454,12 +2089,12 @@ fn should_reset_h1(state: &GameState, settings: &Settings, splitter: &SplitterSt
     let load_indicator_old = old!(state.mcc_loadindicator)?;
 
     let target_level = if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
-        splitter.started_level.as_str()
+        splitter.started_level
     } else {
-        "a10"
+        Some(H1Level::FIRST.into())
     };
 
-    if level_str == target_level {
+    if level == target_level && (level.is_some() || on_started_map(state, splitter, MCCGame::Halo1)) {
         return Some((igt < igt_old && igt < 10) || (load_indicator == 0 && load_indicator_old == 1 && tickcounter < 60));
     }
 
@@ -1471,7 +2106,7 @@ fn should_reset_h2(state: &GameState, settings: &Settings, splitter: &SplitterSt
         return Some(false);
     }
 
-    let level = current!(state.h2_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.h2_levelname, H2Level);
     let igt = current!(state.h2_igt)?;
     let igt_old = old!(state.h2_igt)?;
     let tickcounter = current!(state.h2_tickcounter)?;
@@ -1479,11 +2114,12 @@ fn should_reset_h2(state: &GameState, settings: &Settings, splitter: &SplitterSt
     let load_indicator_old = old!(state.mcc_loadindicator)?;
 
     if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
-        if level == splitter.started_level.as_str() {
+        if level.map(Level::from) == splitter.started_level && (level.is_some() || on_started_map(state, splitter, MCCGame::Halo2)) {
             return Some((igt < igt_old && igt < 10) || (load_indicator == 1 && igt == 0));
         }
     } else {
-        if level == "01a" || (level == "01b" && splitter.started_level.as_str() != "01a") || level == "00a" {
+        let started_on_armory = splitter.started_level == Some(H2Level::FIRST.into());
+        if level.is_some_and(|level| level == H2Level::FIRST || level.is_intro()) || (level == Some(H2Level::Cairo) && !started_on_armory) {
             return Some((igt < igt_old && igt < 10) || (load_indicator == 0 && load_indicator_old == 1 && tickcounter < 60));
         }
     }
@@ -1496,7 +2132,7 @@ fn should_reset_h3(state: &GameState, settings: &Settings, splitter: &SplitterSt
         return Some(false);
     }
 
-    let level = current!(state.h3_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.h3_levelname, H3Level);
     let igt_float = current!(state.mcc_igt_float)?;
     let igt_float_old = old!(state.mcc_igt_float)?;
     let theatertime = current!(state.h3_theatertime)?;
@@ -1506,13 +2142,15 @@ fn should_reset_h3(state: &GameState, settings: &Settings, splitter: &SplitterSt
     let load_indicator_old = old!(state.mcc_loadindicator)?;
 
     if settings.level_mode == LevelMode::IndividualLevel {
-        return Some(level == splitter.started_level.as_str() && igt_float < igt_float_old && igt_float < 0.167);
+        let started_here = level.map(Level::from) == splitter.started_level && (level.is_some() || on_started_map(state, splitter, MCCGame::Halo3));
+        return Some(started_here && igt_float < igt_float_old && igt_float < 0.167);
     } else {
         if settings.any_level {
-            return Some(level == splitter.started_level.as_str() && theatertime > 0 && theatertime < 15);
-        } else if level == "005" {
+            let started_here = level.map(Level::from) == splitter.started_level && (level.is_some() || on_started_map(state, splitter, MCCGame::Halo3));
+            return Some(started_here && theatertime > 0 && theatertime < 15);
+        } else if level.is_some_and(H3Level::is_intro) {
             return Some(load_indicator == 0 && load_indicator_old == 1 && tickcounter < 60);
-        } else if level == "010" {
+        } else if level == Some(H3Level::FIRST) {
             return Some(
                 (theatertime > 0 && theatertime < 15) || (theatertime >= 15 && tickcounter < tickcounter_old && tickcounter < 10 && load_indicator == 0),
             );
@@ -1527,18 +2165,18 @@ fn should_reset_h4(state: &GameState, settings: &Settings, splitter: &SplitterSt
         return Some(false);
     }
 
-    let level = current!(state.h4_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.h4_levelname, H4Level).map(Level::from);
     let igt_float = current!(state.mcc_igt_float)?;
     let igt_float_old = old!(state.mcc_igt_float)?;
     let load_indicator = current!(state.mcc_loadindicator)?;
 
     let target_level = if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
-        splitter.started_level.as_str()
+        splitter.started_level
     } else {
-        "m10"
+        Some(H4Level::FIRST.into())
     };
 
-    if level == target_level {
+    if level == target_level && (level.is_some() || on_started_map(state, splitter, MCCGame::Halo4)) {
         return Some((igt_float < igt_float_old && igt_float < 0.167) || (load_indicator == 1 && igt_float == 0.0));
     }
 
@@ -1550,18 +2188,19 @@ fn should_reset_odst(state: &GameState, settings: &Settings, splitter: &Splitter
         return Some(false);
     }
 
-    let level = current!(state.odst_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.odst_levelname, OdstLevel);
     let streets = current!(state.odst_streets)?;
     let igt_float = current!(state.mcc_igt_float)?;
     let igt_float_old = old!(state.mcc_igt_float)?;
     let load_indicator = current!(state.mcc_loadindicator)?;
 
     if settings.any_level || settings.level_mode == LevelMode::IndividualLevel {
-        if level == splitter.started_level.as_str() && splitter.started_scene == streets {
+        let started_here = level.map(Level::from) == splitter.started_level && (level.is_some() || on_started_map(state, splitter, MCCGame::ODST));
+        if started_here && splitter.started_scene == streets {
             return Some((igt_float < igt_float_old && igt_float < 0.167) || (load_indicator == 1 && igt_float == 0.0));
         }
     } else {
-        if level == "c100" || (level == "h100" && streets == 0) {
+        if level.is_some_and(OdstLevel::is_intro) || (level == Some(OdstLevel::FIRST) && streets == 0) {
             return Some((igt_float < igt_float_old && igt_float < 0.167) || (load_indicator == 1 && igt_float == 0.0));
         }
     }
@@ -1574,18 +2213,18 @@ fn should_reset_hr(state: &GameState, settings: &Settings, splitter: &SplitterSt
         return Some(false);
     }
 
-    let level = current!(state.hr_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.hr_levelname, HrLevel).map(Level::from);
     let igt_float = current!(state.mcc_igt_float)?;
     let igt_float_old = old!(state.mcc_igt_float)?;
     let load_indicator = current!(state.mcc_loadindicator)?;
 
     let target_level = if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
-        splitter.started_level.as_str()
+        splitter.started_level
     } else {
-        "m10"
+        Some(HrLevel::FIRST.into())
     };
 
-    if level == target_level {
+    if level == target_level && (level.is_some() || on_started_map(state, splitter, MCCGame::Reach)) {
         return Some((igt_float < igt_float_old && igt_float < 0.167) || (load_indicator == 1 && igt_float == 0.0));
     }
 
@@ -1630,19 +2269,19 @@ fn should_split(state: &GameState, settings: &Settings, splitter: &mut SplitterS
 }
 
 fn should_split_h1(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.h1_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.h1_levelname, H1Level);
     let bspstate = current!(state.h1_bspstate)?;
     let bspstate_old = old!(state.h1_bspstate)?;
     let load_indicator = current!(state.mcc_loadindicator)?;
     let load_indicator_old = old!(state.mcc_loadindicator)?;
 
     // BSP mode splitting
-    if settings.bsp_mode && bspstate != bspstate_old {
-        let bsp_list = get_h1_bsp_list(&level);
+    if settings.bsp_splits(level.map(Level::from)) && bspstate != bspstate_old {
+        let bsp_list = level.map(get_h1_bsp_list).unwrap_or_default();
         if bsp_list.contains(&bspstate) {
             if settings.bsp_cache || !splitter.contains_dirty_bsp_byte(bspstate) {
                 // Special handling for b40 and c40
-                if level == "b40" && bspstate == 0 {
+                if level == Some(H1Level::AssaultOnTheControlRoom) && bspstate == 0 {
                     let ypos = current!(state.h1_ypos)?;
                     if ypos > -19.544 && ypos < -19.144 {
                         if !settings.bsp_cache {
@@ -1651,7 +2290,7 @@ fn should_split_h1(state: &GameState, settings: &Settings, splitter: &mut Splitt
                         return Some(true);
                     }
                     return Some(false);
-                } else if level == "c40" && bspstate == 0 {
+                } else if level == Some(H1Level::TwoBetrayals) && bspstate == 0 {
                     let xpos = current!(state.h1_xpos)?;
                     let ypos = current!(state.h1_ypos)?;
                     if xpos > 171.87326 && xpos < 185.818526 && ypos > -295.3629 && ypos < -284.356986 {
@@ -1683,18 +2322,18 @@ fn should_split_h1(state: &GameState, settings: &Settings, splitter: &mut Splitt
         let deathflag = current!(state.h1_deathflag)?;
         let tickcounter = current!(state.h1_tickcounter)?;
 
-        let should_split = match level.as_str() {
-            "a10" => bspstate == 6 && !cutsceneskip_old && cutsceneskip,
-            "a30" => bspstate == 1 && !cutsceneskip_old && cutsceneskip,
-            "a50" => (bspstate == 3 || bspstate == 2) && !cutsceneskip_old && cutsceneskip && fadelength == 15,
-            "b30" => bspstate == 0 && !cinematic && !cutsceneskip_old && cutsceneskip,
-            "b40" => bspstate == 2 && !cutsceneskip_old && cutsceneskip,
-            "c10" => bspstate != 2 && !cutsceneskip_old && cutsceneskip,
-            "c20" => cinematic && !cinematic_old && tickcounter > 30,
-            "c40" => tickcounter > 30 && !cutsceneskip_old && cutsceneskip && fadebyte != 1,
-            "d20" => fadelength == 30 && !cinematic_old && cinematic,
-            "d40" => !cinematic_old && cinematic && !cutsceneskip && xpos > 1000.0 && !deathflag,
-            _ => false,
+        let should_split = match level {
+            Some(H1Level::PillarOfAutumn) => bspstate == 6 && !cutsceneskip_old && cutsceneskip,
+            Some(H1Level::Halo) => bspstate == 1 && !cutsceneskip_old && cutsceneskip,
+            Some(H1Level::TruthAndReconciliation) => (bspstate == 3 || bspstate == 2) && !cutsceneskip_old && cutsceneskip && fadelength == 15,
+            Some(H1Level::SilentCartographer) => bspstate == 0 && !cinematic && !cutsceneskip_old && cutsceneskip,
+            Some(H1Level::AssaultOnTheControlRoom) => bspstate == 2 && !cutsceneskip_old && cutsceneskip,
+            Some(H1Level::GuiltySpark) => bspstate != 2 && !cutsceneskip_old && cutsceneskip,
+            Some(H1Level::Library) => cinematic && !cinematic_old && tickcounter > 30,
+            Some(H1Level::TwoBetrayals) => tickcounter > 30 && !cutsceneskip_old && cutsceneskip && fadebyte != 1,
+            Some(H1Level::Keyes) => fadelength == 30 && !cinematic_old && cinematic,
+            Some(H1Level::Maw) => !cinematic_old && cinematic && !cutsceneskip && xpos > 1000.0 && !deathflag,
+            None => false,
         };
 
         if should_split {
@@ -1718,29 +2357,28 @@ fn should_split_h1(state: &GameState, settings: &Settings, splitter: &mut Splitt
 }
 
 fn should_split_h2(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.h2_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.h2_levelname, H2Level);
     let bspstate = current!(state.h2_bspstate)?;
     let bspstate_old = old!(state.h2_bspstate)?;
     let load_indicator = current!(state.mcc_loadindicator)?;
     let load_indicator_old = old!(state.mcc_loadindicator)?;
 
     // BSP mode
-    if settings.bsp_mode && bspstate != bspstate_old {
+    if settings.bsp_splits(level.map(Level::from)) && bspstate != bspstate_old {
+        let bsp_list = level.map(get_h2_bsp_list).unwrap_or_default();
         if settings.bsp_cache {
-            let bsp_list = get_h2_bsp_list(&level);
             if bsp_list.contains(&bspstate) {
                 return Some(true);
             }
         } else {
             // Special TGJ handling
-            if level == "08b" {
+            if level == Some(H2Level::GreatJourney) {
                 return should_split_h2_tgj(state, splitter);
             }
 
             // Other level-specific handling
-            match level.as_str() {
-                "01b" => {
-                    let bsp_list = get_h2_bsp_list(&level);
+            match level {
+                Some(H2Level::Cairo) => {
                     if bsp_list.contains(&bspstate) && !splitter.contains_dirty_bsp_byte(bspstate) {
                         if bspstate == 0 && !splitter.contains_dirty_bsp_byte(2) {
                             return Some(false);
@@ -1749,8 +2387,7 @@ fn should_split_h2(state: &GameState, settings: &Settings, splitter: &mut Splitt
                         return Some(true);
                     }
                 }
-                "04a" => {
-                    let bsp_list = get_h2_bsp_list(&level);
+                Some(H2Level::Arbiter) => {
                     if bsp_list.contains(&bspstate) && !splitter.contains_dirty_bsp_byte(bspstate) {
                         if bspstate == 0 && !splitter.contains_dirty_bsp_byte(3) {
                             return Some(false);
@@ -1759,11 +2396,10 @@ fn should_split_h2(state: &GameState, settings: &Settings, splitter: &mut Splitt
                         return Some(true);
                     }
                 }
-                "04b" => {
+                Some(H2Level::Oracle) => {
                     if bspstate == 3 && !splitter.contains_dirty_bsp_byte(3) {
                         splitter.add_dirty_bsp_byte(3);
                     }
-                    let bsp_list = get_h2_bsp_list(&level);
                     if bsp_list.contains(&bspstate) && !splitter.contains_dirty_bsp_byte(bspstate) {
                         if bspstate == 0 && splitter.contains_dirty_bsp_byte(3) {
                             return Some(true);
@@ -1772,8 +2408,7 @@ fn should_split_h2(state: &GameState, settings: &Settings, splitter: &mut Splitt
                         return Some(true);
                     }
                 }
-                "08a" => {
-                    let bsp_list = get_h2_bsp_list(&level);
+                Some(H2Level::Uprising) => {
                     if bsp_list.contains(&bspstate) && !splitter.contains_dirty_bsp_byte(bspstate) {
                         if bspstate == 0 && !splitter.contains_dirty_bsp_byte(1) {
                             return Some(false);
@@ -1783,7 +2418,6 @@ fn should_split_h2(state: &GameState, settings: &Settings, splitter: &mut Splitt
                     }
                 }
                 _ => {
-                    let bsp_list = get_h2_bsp_list(&level);
                     if bsp_list.contains(&bspstate) && !splitter.contains_dirty_bsp_byte(bspstate) {
                         splitter.add_dirty_bsp_byte(bspstate);
                         return Some(true);
@@ -1795,7 +2429,7 @@ fn should_split_h2(state: &GameState, settings: &Settings, splitter: &mut Splitt
 
     // Full game split
     if !(settings.level_mode == LevelMode::IndividualLevel || settings.igt_mode) {
-        if load_indicator == 1 && load_indicator_old == 0 && level != "00a" {
+        if load_indicator == 1 && load_indicator_old == 0 && !level.is_some_and(H2Level::is_intro) {
             splitter.clear_dirty_bsps();
             return Some(true);
         }
@@ -1855,23 +2489,17 @@ fn should_split_h2_tgj(state: &GameState, splitter: &mut SplitterState) -> Optio
 }
 
 fn should_split_h3(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.h3_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.h3_levelname, H3Level);
     let bspstate = current!(state.h3_bspstate)?;
     let bspstate_old = old!(state.h3_bspstate)?;
     let load_indicator = current!(state.mcc_loadindicator)?;
     let load_indicator_old = old!(state.mcc_loadindicator)?;
 
     // BSP mode
-    if settings.bsp_mode && bspstate != bspstate_old {
-        let bsp_list = get_h3_bsp_list(&level);
-
-        if settings.bsp_cache {
-            if bsp_list.contains(&bspstate) {
-                return Some(true);
-            }
-        } else {
-            if bsp_list.contains(&bspstate) && !splitter.contains_dirty_bsp_long(bspstate) {
-                splitter.add_dirty_bsp_long(bspstate);
+    if settings.bsp_splits(level.map(Level::from)) && bspstate != bspstate_old {
+        if let Some(level) = level {
+            let bsp_splits = get_h3_bsp_splits(level);
+            if should_split_bsp_mask(settings, splitter, level.into(), &bsp_splits, bspstate, bspstate_old, load_indicator == 1) {
                 return Some(true);
             }
         }
@@ -1889,7 +2517,7 @@ fn should_split_h3(state: &GameState, settings: &Settings, splitter: &mut Splitt
 }
 
 fn should_split_h4(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.h4_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.h4_levelname, H4Level);
     let bspstate = current!(state.h4_bspstate)?;
     let bspstate_old = old!(state.h4_bspstate)?;
     let comptimerstate = current!(state.mcc_comptimerstate)?;
@@ -1902,17 +2530,10 @@ fn should_split_h4(state: &GameState, settings: &Settings, splitter: &mut Splitt
         if load_indicator == 0 && pgcr_indicator == 0 && comptimerstate != comptimerstate_old && comptimerstate != 0 && igt_float > 2.0 {
             return Some(true);
         }
-    } else if settings.bsp_mode && bspstate != bspstate_old {
-        let bsp_list = get_h4_bsp_list(&level);
-
-        // H4 uses inverted check - split if NOT in list
-        if settings.bsp_cache {
-            if !bsp_list.contains(&bspstate) {
-                return Some(true);
-            }
-        } else {
-            if !bsp_list.contains(&bspstate) && !splitter.contains_dirty_bsp_long(bspstate) {
-                splitter.add_dirty_bsp_long(bspstate);
+    } else if settings.bsp_splits(level.map(Level::from)) && bspstate != bspstate_old {
+        if let Some(level) = level {
+            let bsp_splits = get_h4_bsp_splits(level);
+            if should_split_bsp_mask(settings, splitter, level.into(), &bsp_splits, bspstate, bspstate_old, load_indicator == 1) {
                 return Some(true);
             }
         }
@@ -1922,7 +2543,7 @@ fn should_split_h4(state: &GameState, settings: &Settings, splitter: &mut Splitt
 }
 
 fn should_split_odst(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.odst_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.odst_levelname, OdstLevel);
     let bspstate = current!(state.odst_bspstate)?;
     let bspstate_old = old!(state.odst_bspstate)?;
     let comptimerstate = current!(state.mcc_comptimerstate)?;
@@ -1932,7 +2553,7 @@ fn should_split_odst(state: &GameState, settings: &Settings, splitter: &mut Spli
     let pgcr_indicator = current!(state.mcc_pgcrindicator)?;
 
     if settings.comp_splits {
-        let invalid_state = if level == "l300" { 876414390 } else { 0 };
+        let invalid_state = if level == Some(OdstLevel::CoastalHighway) { 876414390 } else { 0 };
         if load_indicator == 0
             && pgcr_indicator == 0
             && comptimerstate != comptimerstate_old
@@ -1942,19 +2563,13 @@ fn should_split_odst(state: &GameState, settings: &Settings, splitter: &mut Spli
         {
             return Some(true);
         }
-    } else if settings.bsp_mode && bspstate != bspstate_old {
-        let bsp_list = get_odst_bsp_list(&level);
-
-        if igt_float > 0.5 {
-            if settings.bsp_cache {
-                if bsp_list.contains(&bspstate) {
-                    return Some(true);
-                }
-            } else {
-                if bsp_list.contains(&bspstate) && !splitter.contains_dirty_bsp_int(bspstate) {
-                    splitter.add_dirty_bsp_int(bspstate);
-                    return Some(true);
-                }
+    } else if settings.bsp_splits(level.map(Level::from)) && bspstate != bspstate_old {
+        // The level is still coming in over its first half second of game time
+        let loading = load_indicator == 1 || igt_float <= 0.5;
+        if let Some(level) = level {
+            let bsp_splits = get_odst_bsp_splits(level);
+            if should_split_bsp_mask(settings, splitter, level.into(), &bsp_splits, bspstate.into(), bspstate_old.into(), loading) {
+                return Some(true);
             }
         }
     }
@@ -1963,20 +2578,16 @@ fn should_split_odst(state: &GameState, settings: &Settings, splitter: &mut Spli
 }
 
 fn should_split_hr(state: &GameState, settings: &Settings, splitter: &mut SplitterState) -> Option<bool> {
-    let level = current!(state.hr_levelname)?.validate_utf8().ok()?.to_string();
+    let level = level!(state.hr_levelname, HrLevel);
     let bspstate = current!(state.hr_bspstate)?;
     let bspstate_old = old!(state.hr_bspstate)?;
+    let load_indicator = current!(state.mcc_loadindicator)?;
 
-    if settings.bsp_mode && bspstate != bspstate_old {
-        let bsp_list = get_hr_bsp_list(&level);
-
-        if settings.bsp_cache {
-            if bsp_list.contains(&bspstate) {
-                return Some(true);
-            }
-        } else {
-            if bsp_list.contains(&bspstate) && !splitter.contains_dirty_bsp_int(bspstate) {
-                splitter.add_dirty_bsp_int(bspstate);
+    if settings.bsp_splits(level.map(Level::from)) && bspstate != bspstate_old {
+        if let Some(level) = level {
+            let bsp_splits = get_hr_bsp_splits(level);
+            let loading = load_indicator == 1;
+            if should_split_bsp_mask(settings, splitter, level.into(), &bsp_splits, bspstate.into(), bspstate_old.into(), loading) {
                 return Some(true);
             }
         }
@@ -1985,7 +2596,58 @@ fn should_split_hr(state: &GameState, settings: &Settings, splitter: &mut Splitt
     Some(false)
 }
 
-fn handle_loading(state: &GameState, settings: &Settings, splitter: &mut SplitterState, current_game: MCCGame, menu_indicator: u8, load_indicator: u8) {
+// Splits when a BSP of the table was loaded (or one of its unload BSPs unloaded) this tick,
+// unless that already happened earlier in the level. With the BSP cache setting every load of
+// it splits again. BSPs loaded together make a single split. Whatever gets loaded with the
+// level, while loading or from an empty mask, counts as already loaded and doesn't split.
+fn should_split_bsp_mask(
+    settings: &Settings,
+    splitter: &mut SplitterState,
+    level: Level,
+    bsp_splits: &BspSplits,
+    bspstate: u64,
+    bspstate_old: u64,
+    loading: bool,
+) -> bool {
+    let new_level = splitter.bsp_level != Some(level);
+    if new_level {
+        splitter.clear_dirty_bsps();
+        splitter.bsp_level = Some(level);
+    }
+    if new_level || loading || bspstate_old == 0 {
+        for bsp in bsp_indices(bspstate) {
+            splitter.add_dirty_bsp_byte(bsp);
+        }
+        return false;
+    }
+
+    let mut split = false;
+    for bsp in bsp_indices(bspstate & !bspstate_old).filter(|&bsp| bsp_splits.contains(bsp)) {
+        if settings.bsp_cache || !splitter.contains_dirty_bsp_byte(bsp) {
+            splitter.add_dirty_bsp_byte(bsp);
+            split = true;
+        }
+    }
+    for bsp in bsp_indices(bspstate_old & !bspstate).filter(|&bsp| bsp_splits.contains_unload(bsp)) {
+        if settings.bsp_cache || !splitter.dirty_bsp_unloads.contains(&bsp) {
+            if !splitter.dirty_bsp_unloads.contains(&bsp) {
+                splitter.dirty_bsp_unloads.push(bsp);
+            }
+            split = true;
+        }
+    }
+    split
+}
+
+fn handle_loading(
+    state: &GameState,
+    settings: &Settings,
+    splitter: &mut SplitterState,
+    timer: &mut impl TimerBackend,
+    current_game: MCCGame,
+    menu_indicator: u8,
+    load_indicator: u8,
+) {
     // Check for multigame pause/resume
     if !splitter.multigame_pause && settings.level_mode == LevelMode::FullGame {
         if check_multigame_pause(state, settings, splitter, current_game).unwrap_or(false) {
@@ -1993,6 +2655,7 @@ fn handle_loading(state: &GameState, settings: &Settings, splitter: &mut Splitte
 
             // Store current game time for multigame (we track it ourselves)
             splitter.multigame_time = splitter.game_time;
+            splitter.igt = IgtAccumulator::default();
 
             // Set force split for end of game
             if current_game == MCCGame::Halo1 || current_game == MCCGame::Halo2 {
@@ -2006,19 +2669,10 @@ fn handle_loading(state: &GameState, settings: &Settings, splitter: &mut Splitte
         }
     } else if splitter.multigame_pause {
         if check_multigame_resume(state, current_game).unwrap_or(false) {
-            splitter.multigame_pause = false;
+            resume_multigame(settings, splitter, current_game);
         }
     }
 
-    // Pause timer logic
-    let should_pause = splitter.multigame_pause || (settings.menu_pause && (load_indicator == 1 || menu_indicator == 0)) || splitter.loading;
-
-    if should_pause {
-        asr::timer::pause_game_time();
-    } else {
-        asr::timer::resume_game_time();
-    }
-
     // Handle RTA load removal for H1 and H2
     if !splitter.multigame_pause {
         match current_game {
@@ -2032,14 +2686,25 @@ fn handle_loading(state: &GameState, settings: &Settings, splitter: &mut Splitte
         }
     }
 
+    // H3 coop keeps timing through the pause screen, which MCC reports as a menu
+    let pause_screen = settings.h3_coop && current_game == MCCGame::Halo3 && current!(state.mcc_pauseindicator) == Some(1);
+    let in_game = menu_indicator == 1 || pause_screen;
+
+    // Pause timer logic
+    let should_pause = splitter.multigame_pause || (settings.menu_pause && (load_indicator == 1 || !in_game)) || splitter.loading;
+
+    if should_pause {
+        timer.pause_game_time();
+    } else {
+        timer.resume_game_time();
+    }
+
     // Update game time for IGT-based games
-    if menu_indicator == 1 && !splitter.multigame_pause {
-        update_game_time(state, settings, splitter, current_game);
+    if in_game && !splitter.multigame_pause {
+        update_game_time(state, settings, splitter, timer, current_game);
     }
 }
-fn update_game_time(state: &GameState, settings: &Settings, splitter: &mut SplitterState, current_game: MCCGame) {
-    // TODO: This is all super borked
-
+fn update_game_time(state: &GameState, settings: &Settings, splitter: &mut SplitterState, timer: &mut impl TimerBackend, current_game: MCCGame) {
     // Only handle IGT for games that use it (H3, H4, ODST, Reach) or when igt_mode is on
     let uses_igt = settings.igt_mode || matches!(current_game, MCCGame::Halo3 | MCCGame::Halo4 | MCCGame::ODST | MCCGame::Reach);
 
@@ -2056,7 +2721,7 @@ fn update_game_time(state: &GameState, settings: &Settings, splitter: &mut Split
     let Some(pgcr_indicator_old) = old!(state.mcc_pgcrindicator) else { return };
 
     // Get IGT and tickrate based on game
-    let (igt, igt_old, tickrate): (u32, u32, u8) = match current_game {
+    let (igt, igt_old, tickrate): (u32, u32, u32) = match current_game {
         MCCGame::Halo1 => {
             let Some(igt) = current!(state.h1_igt) else { return };
             let Some(igt_old) = old!(state.h1_igt) else { return };
@@ -2068,7 +2733,13 @@ fn update_game_time(state: &GameState, settings: &Settings, splitter: &mut Split
             (igt, igt_old, 60)
         }
         MCCGame::Halo3 => {
-            if settings.level_mode == LevelMode::IndividualLevel {
+            if settings.h3_coop {
+                // Coop is timed in real time. The tick counter keeps running while the pause
+                // screen is open, unlike theater time, and stops during loads.
+                let Some(igt) = current!(state.h3_tickcounter) else { return };
+                let Some(igt_old) = old!(state.h3_tickcounter) else { return };
+                (igt, igt_old, 60)
+            } else if settings.level_mode == LevelMode::IndividualLevel {
                 let Some(igt_float) = current!(state.mcc_igt_float) else { return };
                 let Some(igt_float_old) = old!(state.mcc_igt_float) else { return };
                 let igt = (igt_float * 60.0).round() as u32;
@@ -2090,56 +2761,27 @@ fn update_game_time(state: &GameState, settings: &Settings, splitter: &mut Split
         _ => return,
     };
 
-    // Track level time
-    if splitter.level_time == 0 {
-        if load_indicator == 0 && !splitter.pgcr_exists {
-            splitter.level_time = igt;
-        }
-    } else if igt > igt_old && (igt - igt_old) < 300 {
-        splitter.level_time += igt - igt_old;
+    // A new game or the first tick of the run
+    if splitter.igt.tickrate() != tickrate {
+        splitter.igt = IgtAccumulator::new(tickrate);
+    }
+
+    let loading = load_indicator == 1;
+    if igt < igt_old && igt < 10 && !loading {
+        splitter.igt.restart(igt, igt_old, settings.igt_add);
     }
+    splitter.igt.update(igt, igt_old, loading);
 
     // Handle PGCR (level complete) or loading screen
     if pgcr_indicator == 1 && pgcr_indicator_old == 0 {
-        let rounded = splitter.level_time - (splitter.level_time % tickrate as u32);
-        splitter.ingame_time += rounded;
-        splitter.level_time = 0;
-        splitter.pgcr_exists = true;
-        splitter.force_split = true;
-    } else if load_indicator == 1 && load_indicator_old == 0 {
-        if !splitter.pgcr_exists {
-            let rounded = splitter.level_time - (splitter.level_time % tickrate as u32);
-            splitter.ingame_time += rounded;
-            splitter.level_time = 0;
-            splitter.force_split = true;
-        }
-        splitter.pgcr_exists = false;
-    } else if igt < igt_old && igt < 10 && load_indicator == 0 {
-        // Level restart
-        let rounded = if settings.igt_add {
-            splitter.level_time
-        } else if splitter.level_time < tickrate as u32 && igt_old < tickrate as u32 {
-            tickrate as u32
-        } else if (splitter.level_time % tickrate as u32) > (tickrate as u32 / 2) {
-            splitter.level_time + (tickrate as u32 - (splitter.level_time % tickrate as u32))
-        } else {
-            splitter.level_time - (splitter.level_time % tickrate as u32)
-        };
-        splitter.ingame_time += rounded;
-        splitter.level_time = 0;
+        splitter.force_split |= splitter.igt.pgcr(igt);
+    } else if loading && load_indicator_old == 0 {
+        splitter.force_split |= splitter.igt.load(igt);
     }
 
-    // Calculate and set game time
-    let total_ticks = if load_indicator == 1 {
-        splitter.ingame_time
-    } else {
-        splitter.ingame_time + splitter.level_time
-    };
-
-    let ms = (1000.0 / tickrate as f64) * total_ticks as f64;
-    splitter.game_time = asr::time::Duration::milliseconds(ms as i64) + splitter.multigame_time;
-
-    asr::timer::set_game_time(splitter.game_time);
+    splitter.game_time = splitter.igt.time(loading) + splitter.multigame_time;
+    timer.set_game_time(splitter.game_time);
+    update_igt_vars(splitter, timer);
 }
 
 fn handle_h1_loading(state: &GameState, splitter: &mut SplitterState, load_indicator: u8) {
@@ -2165,40 +2807,56 @@ fn handle_h1_loading(state: &GameState, splitter: &mut SplitterState, load_indic
     }
 }
 
+// Tells loads apart from internal cutscenes and pause screens. The level end fade is timed,
+// since internal cutscenes fade out the same way; only a loading screen or BSP 255 make it a
+// load. After a load the timer resumes once the new level fades in, which needs the tick
+// counter running again so a paused or frozen game doesn't count.
 fn handle_h2_loading(state: &GameState, splitter: &mut SplitterState, load_indicator: u8) {
-    // TODO: This is broken and doesn't take into account internal cutscenes
-
     let Some(menu_indicator) = current!(state.mcc_menuindicator) else { return };
-    let Some(load_indicator_old) = old!(state.mcc_loadindicator) else { return };
     let Some(fadebyte) = current!(state.h2_fadebyte) else { return };
-    let Some(fadebyte_old) = old!(state.h2_fadebyte) else { return };
-    let Some(letterbox) = current!(state.h2_letterbox) else { return };
-    let Some(letterbox_old) = old!(state.h2_letterbox) else { return };
     let Some(tickcounter) = current!(state.h2_tickcounter) else { return };
+    let Some(tickcounter_old) = old!(state.h2_tickcounter) else { return };
     let Some(bspstate) = current!(state.h2_bspstate) else { return };
     let Some(pause_indicator) = current!(state.mcc_pauseindicator) else { return };
-
-    if !splitter.loading {
-        if menu_indicator == 1 {
-            // Check for level end fade
-            if (tickcounter > 60 && fadebyte == 1 && fadebyte_old == 1 && letterbox > 0.96 && letterbox_old <= 0.96 && letterbox_old != 0.0)
-                || load_indicator == 1
-            {
-                splitter.loading = true;
+    let Some(fade_finished) = h2_fade_finished(state) else { return };
+
+    let in_game = menu_indicator == 1;
+    let unloaded = load_indicator == 1 || (in_game && bspstate == 255);
+    let ticking = tickcounter == tickcounter_old + 1 && pause_indicator == 0;
+
+    splitter.h2_load_state = match splitter.h2_load_state {
+        _ if unloaded => H2LoadState::Loading,
+        H2LoadState::Playing => H2LoadState::Playing,
+        H2LoadState::Loading => {
+            if in_game {
+                H2LoadState::FadeIn { faded_out: fadebyte == 1 }
+            } else {
+                H2LoadState::Loading
             }
-        } else if load_indicator == 1 && load_indicator_old == 0 {
-            splitter.loading = true;
         }
-    } else {
-        if menu_indicator == 1 && load_indicator == 0 {
-            if fadebyte == 0 && fadebyte_old == 1 && pause_indicator == 0 && bspstate != 255 {
-                splitter.loading = false;
-            } else if fadebyte == 0 && tickcounter > 10 && bspstate != 255 {
-                splitter.loading = false;
+        H2LoadState::FadeIn { faded_out } => {
+            let faded_out = faded_out || fadebyte == 1;
+            if in_game && ticking && ((fadebyte == 1 && fade_finished) || (fadebyte == 0 && (faded_out || tickcounter > 10))) {
+                H2LoadState::Playing
+            } else {
+                H2LoadState::FadeIn { faded_out }
             }
         }
-    }
+    };
+
+    splitter.loading = matches!(splitter.h2_load_state, H2LoadState::Loading | H2LoadState::FadeIn { .. });
+}
+
+// Whether the current H2 fade has run its length. The fade length is scaled to ticks by the
+// version dependent fadescale.
+fn h2_fade_finished(state: &GameState) -> Option<bool> {
+    let tickcounter = current!(state.h2_tickcounter)?;
+    let fadetick = current!(state.h2_fadetick)?;
+    let fadelength = current!(state.h2_fadelength)?;
+
+    Some(tickcounter >= fadetick.saturating_add((fadelength as f64 * state.fadescale) as u32))
 }
+
 fn check_multigame_pause(state: &GameState, settings: &Settings, splitter: &mut SplitterState, current_game: MCCGame) -> Option<bool> {
     if settings.level_mode == LevelMode::IndividualLevel || settings.any_level {
         return Some(false);
@@ -2207,25 +2865,25 @@ fn check_multigame_pause(state: &GameState, settings: &Settings, splitter: &mut
     match current_game {
         MCCGame::Halo1 => {
             // H1 - PoA ending
-            let level = current!(state.h1_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.h1_levelname, H1Level);
             let cinematic = current!(state.h1_cinematic)?;
             let cinematic_old = old!(state.h1_cinematic)?;
             let cutsceneskip = current!(state.h1_cutsceneskip)?;
             let xpos = current!(state.h1_xpos)?;
             let deathflag = current!(state.h1_deathflag)?;
 
-            Some(level == "d40" && !cinematic_old && cinematic && !cutsceneskip && xpos > 1000.0 && !deathflag)
+            Some(level == Some(H1Level::LAST) && !cinematic_old && cinematic && !cutsceneskip && xpos > 1000.0 && !deathflag)
         }
         MCCGame::Halo2 => {
             // H2 - TGJ ending
-            let level = current!(state.h2_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.h2_levelname, H2Level);
             let fadebyte = current!(state.h2_fadebyte)?;
             let letterbox = current!(state.h2_letterbox)?;
             let letterbox_old = old!(state.h2_letterbox)?;
             let tickcounter = current!(state.h2_tickcounter)?;
 
             Some(
-                level == "08b"
+                level == Some(H2Level::LAST)
                     && fadebyte == 1
                     && letterbox > 0.96
                     && letterbox_old <= 0.96
@@ -2235,111 +2893,340 @@ fn check_multigame_pause(state: &GameState, settings: &Settings, splitter: &mut
             )
         }
         MCCGame::Halo3 => {
-            // H3 - Halo ending
-            let level = current!(state.h3_levelname)?.validate_utf8().ok()?.to_string();
+            // H3 - Epilogue loading after Halo
+            let level = level!(state.h3_levelname, H3Level);
             let load_indicator = current!(state.mcc_loadindicator)?;
             let load_indicator_old = old!(state.mcc_loadindicator)?;
 
-            Some(load_indicator == 1 && load_indicator_old == 0 && level == "130")
+            Some(load_indicator == 1 && load_indicator_old == 0 && level.is_some_and(H3Level::is_outro))
         }
         MCCGame::Halo4 => {
             // H4 - Midnight ending
-            let level = current!(state.h4_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.h4_levelname, H4Level);
             let pgcr = current!(state.mcc_pgcrindicator)?;
             let pgcr_old = old!(state.mcc_pgcrindicator)?;
 
-            Some(pgcr == 1 && pgcr_old == 0 && level == "m90")
+            Some(pgcr == 1 && pgcr_old == 0 && level == Some(H4Level::LAST))
         }
         MCCGame::ODST => {
             // ODST - Coastal ending
-            let level = current!(state.odst_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.odst_levelname, OdstLevel);
             let pgcr = current!(state.mcc_pgcrindicator)?;
             let pgcr_old = old!(state.mcc_pgcrindicator)?;
 
-            Some(pgcr == 1 && pgcr_old == 0 && level == "l300")
+            Some(pgcr == 1 && pgcr_old == 0 && level == Some(OdstLevel::LAST))
         }
         MCCGame::Reach => {
             // Reach - PoA ending
-            let level = current!(state.hr_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.hr_levelname, HrLevel);
             let pgcr = current!(state.mcc_pgcrindicator)?;
             let pgcr_old = old!(state.mcc_pgcrindicator)?;
 
-            Some(pgcr == 1 && pgcr_old == 0 && level == "m70")
+            Some(pgcr == 1 && pgcr_old == 0 && level == Some(HrLevel::LAST))
         }
         _ => Some(false),
     }
 }
 
+// Resumes a run paused between games. With a multigame route only the route's next game
+// resumes, and nothing does after its last game.
+fn resume_multigame(settings: &Settings, splitter: &mut SplitterState, current_game: MCCGame) {
+    let Some(route) = settings.route() else {
+        splitter.multigame_pause = false;
+        return;
+    };
+
+    match route.get(splitter.route_game + 1) {
+        Some(&next) if next == current_game => {
+            splitter.multigame_pause = false;
+            splitter.route_game += 1;
+            splitter.route_out_of_order = None;
+        }
+        Some(_) => splitter.route_out_of_order = Some(current_game),
+        None => {}
+    }
+}
+
 fn check_multigame_resume(state: &GameState, current_game: MCCGame) -> Option<bool> {
     match current_game {
         MCCGame::Halo1 => {
             // H1 - PoA start
-            let level = current!(state.h1_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.h1_levelname, H1Level);
             let bspstate = current!(state.h1_bspstate)?;
             let xpos = current!(state.h1_xpos)?;
             let tickcounter = current!(state.h1_tickcounter)?;
             let cinematic = current!(state.h1_cinematic)?;
             let cinematic_old = old!(state.h1_cinematic)?;
 
-            Some(level == "a10" && bspstate == 0 && xpos < -55.0 && tickcounter > 280 && !cinematic && cinematic_old)
+            Some(level == Some(H1Level::FIRST) && bspstate == 0 && xpos < -55.0 && tickcounter > 280 && !cinematic && cinematic_old)
         }
         MCCGame::Halo2 => {
             // H2 - Armory/Cairo start
-            let level = current!(state.h2_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.h2_levelname, H2Level);
             let tickcounter = current!(state.h2_tickcounter)?;
             let fadebyte = current!(state.h2_fadebyte)?;
             let fadebyte_old = old!(state.h2_fadebyte)?;
             let load_indicator = current!(state.mcc_loadindicator)?;
 
             Some(
-                (level == "01a" && tickcounter >= 26 && tickcounter < 30)
-                    || (level == "01b" && load_indicator == 0 && fadebyte == 0 && fadebyte_old == 1 && tickcounter < 30),
+                (level == Some(H2Level::FIRST) && tickcounter >= 26 && tickcounter < 30)
+                    || (level == Some(H2Level::Cairo) && load_indicator == 0 && fadebyte == 0 && fadebyte_old == 1 && tickcounter < 30),
             )
         }
         MCCGame::Halo3 => {
             // H3 - Sierra start
-            let level = current!(state.h3_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.h3_levelname, H3Level);
             let theatertime = current!(state.h3_theatertime)?;
 
-            Some(level == "010" && theatertime > 15 && theatertime < 30)
+            Some(level == Some(H3Level::FIRST) && theatertime > 15 && theatertime < 30)
         }
         MCCGame::Halo4 => {
             // H4 - Dawn start
-            let level = current!(state.h4_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.h4_levelname, H4Level);
             let igt_float = current!(state.mcc_igt_float)?;
 
-            Some(level == "m10" && igt_float > 0.167 && igt_float < 0.5)
+            Some(level == Some(H4Level::FIRST) && igt_float > 0.167 && igt_float < 0.5)
         }
         MCCGame::ODST => {
             // ODST - Mombasa Streets start
-            let level = current!(state.odst_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.odst_levelname, OdstLevel);
             let streets = current!(state.odst_streets)?;
             let igt_float = current!(state.mcc_igt_float)?;
 
-            Some(level == "h100" && streets == 0 && igt_float > 0.167 && igt_float < 0.5)
+            Some(level == Some(OdstLevel::FIRST) && streets == 0 && igt_float > 0.167 && igt_float < 0.5)
         }
         MCCGame::Reach => {
             // Reach - Winter Contingency start
-            let level = current!(state.hr_levelname)?.validate_utf8().ok()?.to_string();
+            let level = level!(state.hr_levelname, HrLevel);
             let igt_float = current!(state.mcc_igt_float)?;
 
-            Some(level == "m10" && igt_float > 0.167 && igt_float < 0.5)
+            Some(level == Some(HrLevel::FIRST) && igt_float > 0.167 && igt_float < 0.5)
         }
         _ => Some(false),
     }
 }
 
-fn update_death_counter(state: &GameState, splitter: &mut SplitterState, current_game: MCCGame) {
+// Real time measured at once before the H1 speed is checked. Long enough that frame pacing
+// evens out.
+const H1_SPEED_WINDOW: Duration = Duration::seconds(5);
+
+// Compares H1 tick counter progress against real time while the level is running. Ticks that
+// come in more than 5% faster than 30 per second fail the check. Pauses, loads and reverts
+// start a new measurement.
+fn update_h1_speed_check(state: &GameState, splitter: &mut SplitterState, timer: &mut impl TimerBackend, current_game: MCCGame) {
+    let now = timer.now();
+    let last_now = splitter.speed_last_now.replace(now);
+
+    let ticks = if current_game == MCCGame::Halo1 { h1_ticks_played(state) } else { None };
+    let (Some(ticks), Some(last_now)) = (ticks, last_now) else {
+        splitter.speed_ticks = 0;
+        splitter.speed_time = Duration::ZERO;
+        return;
+    };
+
+    splitter.speed_ticks += ticks;
+    splitter.speed_time += now - last_now;
+    if splitter.speed_time < H1_SPEED_WINDOW {
+        return;
+    }
+
+    let tick_time = Duration::milliseconds(splitter.speed_ticks as i64 * 1000 / 30);
+    let drift = tick_time - splitter.speed_time;
+    let valid = drift * 20 <= splitter.speed_time;
+    splitter.is_valid = valid && (splitter.is_valid || splitter.c_time.is_zero());
+    splitter.c_time += splitter.speed_time;
+    splitter.diff += drift;
+    splitter.speed_ticks = 0;
+    splitter.speed_time = Duration::ZERO;
+
+    timer.set_variable("H1 Speed Valid", if splitter.is_valid { "Yes" } else { "No" });
+    timer.set_variable("Tick Drift", &format!("{:+.2}s", splitter.diff.whole_milliseconds() as f64 / 1000.0));
+}
+
+// Ticks the H1 level advanced by since the last read, if it's being played.
+fn h1_ticks_played(state: &GameState) -> Option<u32> {
+    let tickcounter = current!(state.h1_tickcounter)?;
+    let tickcounter_old = old!(state.h1_tickcounter)?;
+    let menu_indicator = current!(state.mcc_menuindicator)?;
+    let load_indicator = current!(state.mcc_loadindicator)?;
+    let pause_indicator = current!(state.mcc_pauseindicator)?;
+
+    if menu_indicator != 1 || load_indicator != 0 || pause_indicator != 0 {
+        return None;
+    }
+    tickcounter.checked_sub(tickcounter_old).filter(|&ticks| ticks > 0 && ticks <= 30)
+}
+
+// Name of the map currently loaded in the given game, campaign level or not. Borrowed from the
+// watcher, as it's checked every tick.
+fn map_name(state: &GameState, game: MCCGame) -> Option<&str> {
+    let name = match game {
+        MCCGame::Halo1 => state.h1_levelname.pair.as_ref()?.current.validate_utf8(),
+        MCCGame::Halo2 => state.h2_levelname.pair.as_ref()?.current.validate_utf8(),
+        MCCGame::Halo3 => state.h3_levelname.pair.as_ref()?.current.validate_utf8(),
+        MCCGame::Halo4 => state.h4_levelname.pair.as_ref()?.current.validate_utf8(),
+        MCCGame::ODST => state.odst_levelname.pair.as_ref()?.current.validate_utf8(),
+        MCCGame::Reach => state.hr_levelname.pair.as_ref()?.current.validate_utf8(),
+        MCCGame::Unknown => return None,
+    };
+    name.ok()
+}
+
+// Whether the map loaded is the one the run started on. Only needed for custom maps, which
+// have no Level to compare.
+fn on_started_map(state: &GameState, splitter: &SplitterState, game: MCCGame) -> bool {
+    map_name(state, game).is_some_and(|name| !name.is_empty() && name == splitter.started_map)
+}
+
+// Campaign level currently loaded in the given game.
+fn current_level(state: &GameState, game: MCCGame) -> Option<Level> {
+    match game {
+        MCCGame::Halo1 => level!(state.h1_levelname, H1Level).map(Level::from),
+        MCCGame::Halo2 => level!(state.h2_levelname, H2Level).map(Level::from),
+        MCCGame::Halo3 => level!(state.h3_levelname, H3Level).map(Level::from),
+        MCCGame::Halo4 => level!(state.h4_levelname, H4Level).map(Level::from),
+        MCCGame::ODST => level!(state.odst_levelname, OdstLevel).map(Level::from),
+        MCCGame::Reach => level!(state.hr_levelname, HrLevel).map(Level::from),
+        MCCGame::Unknown => None,
+    }
+}
+
+// BSP state of the given game widened to a u64.
+fn current_bsp(state: &GameState, game: MCCGame) -> Option<u64> {
+    match game {
+        MCCGame::Halo1 => current!(state.h1_bspstate).map(u64::from),
+        MCCGame::Halo2 => current!(state.h2_bspstate).map(u64::from),
+        MCCGame::Halo3 => current!(state.h3_bspstate),
+        MCCGame::Halo4 => current!(state.h4_bspstate),
+        MCCGame::ODST => current!(state.odst_bspstate).map(u64::from),
+        MCCGame::Reach => current!(state.hr_bspstate).map(u64::from),
+        MCCGame::Unknown => None,
+    }
+}
+
+// H1 and H2 report the index of the loaded BSP, the later games a bitmask of every loaded
+// BSP. Decodes either into a list of indices.
+fn decode_bsp(game: MCCGame, bspstate: u64) -> String {
+    match game {
+        MCCGame::Halo1 | MCCGame::Halo2 => bspstate.to_string(),
+        _ => bsp_indices(bspstate).map(|bsp| bsp.to_string()).collect::<Vec<_>>().join(", "),
+    }
+}
+
+fn update_game_vars(state: &GameState, splitter: &mut SplitterState, timer: &mut impl TimerBackend, current_game: MCCGame) {
+    if splitter.game_var != Some(current_game) {
+        splitter.game_var = Some(current_game);
+        timer.set_variable("Current Game", &current_game.to_string());
+    }
+
+    let bspstate = current_bsp(state, current_game);
+    if bspstate != splitter.bsp_var {
+        splitter.bsp_var = bspstate;
+        match bspstate {
+            Some(bspstate) => {
+                timer.set_variable_int("Current BSP (Raw)", bspstate);
+                timer.set_variable("Current BSP", &decode_bsp(current_game, bspstate));
+            }
+            None => {
+                timer.set_variable("Current BSP (Raw)", "-");
+                timer.set_variable("Current BSP", "-");
+            }
+        }
+    }
+}
+
+// Publishes the current mission's name and where it sits in its campaign, e.g. "3/10" with the
+// next mission's name, or "-" outside the campaign.
+fn update_level_vars(state: &GameState, splitter: &mut SplitterState, timer: &mut impl TimerBackend, current_game: MCCGame) {
+    let level = current_level(state, current_game);
+    if level == splitter.level_var {
+        return;
+    }
+    splitter.level_var = level;
+
+    timer.set_variable("Current Level", level.map_or("-", Level::name));
+    let number = level.and_then(|level| Some(format!("{}/{}", level.number()?, level.missions())));
+    timer.set_variable("Mission Number", number.as_deref().unwrap_or("-"));
+    timer.set_variable("Next Mission", level.and_then(Level::next_mission).map_or("-", Level::name));
+}
+
+// Publishes where a run is along the multigame route, e.g. "Halo 2 (2/3)" or "Waiting for
+// Halo 3" between games. Nothing is published without a route.
+fn update_route_var(settings: &Settings, splitter: &mut SplitterState, timer: &mut impl TimerBackend) {
+    let Some(route) = settings.route() else { return };
+
+    let running = matches!(timer.state(), TimerState::Running | TimerState::Paused | TimerState::Ended);
+    let expected = if running { route.get(splitter.route_game + 1) } else { route.first() };
+    let status = match (splitter.route_out_of_order, expected) {
+        (Some(game), Some(expected)) => format!("{} started out of order, expected {}", game.title(), expected.title()),
+        _ if !running => format!("Waiting for {}", route[0].title()),
+        (_, Some(next)) if splitter.multigame_pause => format!("Waiting for {}", next.title()),
+        (_, None) if splitter.multigame_pause => "Finished".to_string(),
+        _ => format!("{} ({}/{})", route[splitter.route_game].title(), splitter.route_game + 1, route.len()),
+    };
+
+    if splitter.route_var.as_ref() != Some(&status) {
+        timer.set_variable("Multigame Route", &status);
+        splitter.route_var = Some(status);
+    }
+}
+
+fn update_igt_vars(splitter: &mut SplitterState, timer: &mut impl TimerBackend) {
+    let level_igt = splitter.igt.level_time();
+    if splitter.level_igt_var != Some(level_igt) {
+        splitter.level_igt_var = Some(level_igt);
+        timer.set_variable("Level IGT", &format_time(level_igt));
+    }
+    if splitter.run_igt_var != Some(splitter.game_time) {
+        splitter.run_igt_var = Some(splitter.game_time);
+        timer.set_variable("Run IGT", &format_time(splitter.game_time));
+    }
+}
+
+// Formats a time the way LiveSplit shows it, e.g. 1:02:03.450 or 2:03.450.
+fn format_time(time: Duration) -> String {
+    let millis = time.subsec_milliseconds();
+    let seconds = time.whole_seconds() % 60;
+    let minutes = time.whole_minutes() % 60;
+    match time.whole_hours() {
+        0 => format!("{}:{:02}.{:03}", minutes, seconds, millis),
+        hours => format!("{}:{:02}:{:02}.{:03}", hours, minutes, seconds, millis),
+    }
+}
+
+fn update_death_counter(state: &GameState, splitter: &mut SplitterState, timer: &mut impl TimerBackend, current_game: MCCGame) {
+    if let Some(level) = current_level(state, current_game) {
+        if splitter.level_deaths.last().is_none_or(|(last, _)| *last != level) {
+            splitter.level_deaths.push((level, 0));
+            timer.set_variable_int("Deaths (Level)", 0);
+            set_death_history_var(splitter, timer);
+        }
+    }
+
     let died = match current_game {
         MCCGame::Halo1 => changed_to!(state.h1_deathflag, true),
         MCCGame::Halo2 => changed_to!(state.h2_deathflag, true),
         MCCGame::Halo3 => changed_to!(state.h3_deathflag, true),
+        // Halo 4's death flag hasn't been found on any build
+        MCCGame::Halo4 => false,
         MCCGame::ODST => changed_to!(state.odst_deathflag, true),
         MCCGame::Reach => changed_to!(state.hr_deathflag, true),
-        _ => false,
+        MCCGame::Unknown => false,
     };
     if died {
         splitter.death_counter += 1;
-        asr::timer::set_variable_int("Deaths", splitter.death_counter);
+        timer.set_variable_int("Deaths", splitter.death_counter as u64);
+        timer.set_variable_int("Deaths (Total)", splitter.death_counter as u64);
+        if let Some((_, deaths)) = splitter.level_deaths.last_mut() {
+            *deaths += 1;
+            timer.set_variable_int("Deaths (Level)", *deaths as u64);
+            set_death_history_var(splitter, timer);
+        }
     }
 }
+
+// Deaths of every level played so far, e.g. "Dawn: 1, Requiem: 3".
+fn set_death_history_var(splitter: &SplitterState, timer: &mut impl TimerBackend) {
+    let history: Vec<_> = splitter.level_deaths.iter().map(|(level, deaths)| format!("{}: {}", level, deaths)).collect();
+    timer.set_variable("Deaths (History)", &history.join(", "));
+}
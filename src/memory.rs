@@ -0,0 +1,140 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use asr::{deep_pointer::DeepPointer, Address, Process};
+use bytemuck::{CheckedBitPattern, NoUninit};
+
+const MAX_DEPTH: usize = 8;
+
+// Pointer chain of up to 8 offsets from a module base. Unlike asr's DeepPointer this
+// keeps its base and path visible so it can be resolved against any MemorySource.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeepPtr {
+    base: Address,
+    path: [u64; MAX_DEPTH],
+    len: usize,
+}
+
+impl DeepPtr {
+    pub fn new_64bit(base: impl Into<Address>, path: &[u64]) -> Self {
+        let mut ptr = Self {
+            base: base.into(),
+            ..Default::default()
+        };
+        ptr.len = path.len().min(MAX_DEPTH);
+        ptr.path[..ptr.len].copy_from_slice(&path[..ptr.len]);
+        ptr
+    }
+
+    pub fn base(&self) -> Address {
+        self.base
+    }
+
+    pub fn path(&self) -> &[u64] {
+        &self.path[..self.len]
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.base.is_null()
+    }
+
+    pub fn resolve<T: CheckedBitPattern>(&self, source: &impl MemorySource) -> Resolution<T> {
+        if self.len == 0 {
+            return Resolution::Missing;
+        }
+        if self.is_null() {
+            return Resolution::Failed;
+        }
+        match source.read_path(self.base, self.path()) {
+            Some(value) => Resolution::Resolved(value),
+            None => Resolution::Failed,
+        }
+    }
+
+    pub fn deref<T: CheckedBitPattern>(&self, source: &impl MemorySource) -> Option<T> {
+        self.resolve(source).ok()
+    }
+}
+
+// Outcome of reading through a DeepPtr.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resolution<T> {
+    Resolved(T),
+    // The chain was never built because the MCC build has no offsets for it.
+    Missing,
+    // The chain couldn't be followed, because its module isn't loaded or a pointer along
+    // the way is null, e.g. during a load.
+    Failed,
+}
+
+impl<T> Resolution<T> {
+    pub fn ok(self) -> Option<T> {
+        match self {
+            Resolution::Resolved(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    // How the read went, without the value.
+    pub fn status(&self) -> Resolution<()> {
+        match self {
+            Resolution::Resolved(_) => Resolution::Resolved(()),
+            Resolution::Missing => Resolution::Missing,
+            Resolution::Failed => Resolution::Failed,
+        }
+    }
+}
+
+pub trait MemorySource {
+    fn read_path<T: CheckedBitPattern>(&self, base: Address, path: &[u64]) -> Option<T>;
+}
+
+impl MemorySource for Process {
+    fn read_path<T: CheckedBitPattern>(&self, base: Address, path: &[u64]) -> Option<T> {
+        DeepPointer::<MAX_DEPTH>::new_64bit(base, path).deref(self).ok()
+    }
+}
+
+// In-memory stand-in for the MCC process. Values are keyed by the module base and the
+// exact offset chain they are read through, so the pointers built by update_game_pointers
+// can be fed directly without emulating the intermediate pointer hops.
+#[derive(Default)]
+pub struct FakeMemory {
+    values: BTreeMap<(u64, Vec<u64>), Vec<u8>>,
+    zeroed: bool,
+}
+
+impl FakeMemory {
+    // Memory where every pointer that was never set reads as zero instead of failing.
+    pub fn zeroed() -> Self {
+        Self {
+            zeroed: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn set<T: NoUninit>(&mut self, ptr: &DeepPtr, value: T) {
+        self.set_path(ptr.base(), ptr.path(), value);
+    }
+
+    pub fn set_path<T: NoUninit>(&mut self, base: Address, path: &[u64], value: T) {
+        self.values.insert((base.value(), path.to_vec()), bytemuck::bytes_of(&value).to_vec());
+    }
+
+    pub fn remove(&mut self, ptr: &DeepPtr) {
+        self.values.remove(&(ptr.base().value(), ptr.path().to_vec()));
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+impl MemorySource for FakeMemory {
+    fn read_path<T: CheckedBitPattern>(&self, base: Address, path: &[u64]) -> Option<T> {
+        let size = core::mem::size_of::<T>();
+        match self.values.get(&(base.value(), path.to_vec())) {
+            Some(bytes) => bytemuck::checked::try_pod_read_unaligned(bytes.get(..size)?).ok(),
+            None if self.zeroed => bytemuck::checked::try_pod_read_unaligned(&alloc::vec![0u8; size]).ok(),
+            None => None,
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use crate::splitter::H1Checklist;
+
+// Offsets for every supported MCC build. Adding a new patch means adding one entry to
+// OFFSETS; update_game_pointers turns these into pointer chains and fills in the offsets
+// that are the same on every build. The DLLs are identical between Steam and WinStore, only
+// the MCC executable differs.
+#[derive(Clone)]
+pub struct VersionOffsets {
+    pub version: u16,
+    pub mcc: MccOffsets,
+    // None if this build was never supported on the WinStore.
+    pub mcc_winstore: Option<MccOffsets>,
+    pub fadescale: f64,
+    pub h1: H1Offsets,
+    pub h2: H2Offsets,
+    pub h3: H3Offsets,
+    pub hr: HrOffsets,
+    pub odst: OdstOffsets,
+    pub h4: H4Offsets,
+}
+
+#[derive(Clone)]
+pub struct MccOffsets {
+    pub menustate: u64,
+    // Relative to menustate
+    pub menu: u64,
+    pub pause: u64,
+    pub pgcr: u64,
+    pub gameindicator: u64,
+    pub igt_float: u64,
+    pub comptimerstate: u64,
+    pub comptimer: u64,
+}
+
+#[derive(Clone)]
+pub struct H1Offsets {
+    pub tickcounter: u64,
+    pub igt: u64,
+    pub bspstate: u64,
+    pub globals: u64,
+    pub map: u64,
+    pub cinflags: u64,
+    pub coords: u64,
+    pub fade: u64,
+    pub checklist: H1Checklist,
+}
+
+#[derive(Clone)]
+pub struct H2Offsets {
+    pub levelname: u64,
+    pub igt: u64,
+    pub bspstate: u64,
+    pub deathflag: u64,
+    pub tickcounter: u64,
+    pub graphics: u64,
+    pub cinflags: u64,
+    pub coords: u64,
+    pub fade: u64,
+}
+
+#[derive(Clone)]
+pub struct H3Offsets {
+    pub levelname: u64,
+    pub theatertime: u64,
+    pub tickcounter: u64,
+    pub bspstate: u64,
+    pub deathflag: u64,
+    pub deathflag_offset: u64,
+}
+
+#[derive(Clone)]
+pub struct HrOffsets {
+    pub levelname: u64,
+    pub bspstate: u64,
+    pub deathflag: u64,
+    pub deathflag_offset: u64,
+}
+
+#[derive(Clone)]
+pub struct OdstOffsets {
+    pub levelname: u64,
+    pub streets: u64,
+    pub bspstate: u64,
+    pub deathflag: u64,
+}
+
+#[derive(Clone)]
+pub struct H4Offsets {
+    pub levelname: u64,
+    pub bspstate: u64,
+    // Only some builds reach the BSP state through a second pointer.
+    pub bspstate_offset: Option<u64>,
+}
+
+impl VersionOffsets {
+    pub fn mcc(&self, is_winstore: bool) -> Option<&MccOffsets> {
+        if is_winstore {
+            self.mcc_winstore.as_ref()
+        } else {
+            Some(&self.mcc)
+        }
+    }
+}
+
+// Offsets for the given MCC build, None for builds missing from the table.
+pub fn for_version(version: u16) -> Option<&'static VersionOffsets> {
+    OFFSETS.iter().find(|o| o.version == version)
+}
+
+// Offsets to start from for the given MCC build. Builds newer than the last known one get its
+// offsets, which are only a guess until the globals have been found by signature.
+pub fn closest(version: u16) -> Option<&'static VersionOffsets> {
+    let latest = OFFSETS.last()?;
+    if version > latest.version {
+        return Some(latest);
+    }
+    for_version(version)
+}
+
+// Whether the table has an entry for exactly this build.
+pub fn is_known(version: u16) -> bool {
+    OFFSETS.iter().any(|o| o.version == version)
+}
+
+// Sorted by version.
+#[rustfmt::skip]
+pub const OFFSETS: &[VersionOffsets] = &[
+    VersionOffsets {
+        version: 2448,
+        mcc: MccOffsets { menustate: 0x3A24FC4, menu: 0x11, pause: 0xA, pgcr: 0xB, gameindicator: 0x3A253A0, igt_float: 0x3A25188, comptimerstate: 0x3A254B0, comptimer: 0x1A4 },
+        mcc_winstore: None,
+        fadescale: 0.183,
+        h1: H1Offsets {
+            tickcounter: 0x2B58A24, igt: 0x2AF477C, bspstate: 0x19F0400, globals: 0x2AF10D0, map: 0x2A4BC04, cinflags: 0x2AF1868, coords: 0x2A57E74, fade: 0x2B81CE8,
+            checklist: H1Checklist { a10: 2495112808, a30: 1196246201, a50: 3037603536, b30: 682311759, b40: 326064131, c10: 645721511, c20: 540616268, c40: 1500399674, d20: 2770760039, d40: 1695151528 },
+        },
+        h2: H2Offsets { levelname: 0xE63FB3, igt: 0xE22F40, bspstate: 0xCD7D74, deathflag: 0xDA6140, tickcounter: 0xE63144, graphics: 0xCFB918, cinflags: 0x143ACA0, coords: 0xDA5CD8, fade: 0x13DFC58 },
+        h3: H3Offsets { levelname: 0x1D2C460, theatertime: 0x1DDC3BC, tickcounter: 0x2961E0C, bspstate: 0x9F3EF0, deathflag: 0x1CB15C8, deathflag_offset: 0x1051D },
+        hr: HrOffsets { levelname: 0x2868777, bspstate: 0x36778E0, deathflag: 0xEEFEB0, deathflag_offset: 0x544249 },
+        odst: OdstOffsets { levelname: 0x1CDF200, streets: 0x1DB2568, bspstate: 0x2E46964, deathflag: 0xE8520C },
+        h4: H4Offsets { levelname: 0x276ACA3, bspstate: 0x2441AB8, bspstate_offset: Some(-0x560i64 as u64) },
+    },
+    VersionOffsets {
+        version: 2645,
+        mcc: MccOffsets { menustate: 0x3B80E64, menu: 0x11, pause: 0xB, pgcr: 0xC, gameindicator: 0x3B81270, igt_float: 0x3B80FF8, comptimerstate: 0x3B81380, comptimer: 0x1A4 },
+        mcc_winstore: None,
+        fadescale: 0.067,
+        h1: H1Offsets {
+            tickcounter: 0x2B5FC04, igt: 0x2AFB954, bspstate: 0x19F748C, globals: 0x2AF8240, map: 0x2A52D84, cinflags: 0x2AF89B8, coords: 0x2A5EFF4, fade: 0x2B88E58,
+            checklist: H1Checklist { a10: 4031641132, a30: 1497905037, a50: 2613596386, b30: 4057206713, b40: 2439716616, c10: 2597150717, c20: 1656675814, c40: 1573304389, d20: 1507739304, d40: 2038583061 },
+        },
+        h2: H2Offsets { levelname: 0xD42E68, igt: 0x1475C10, bspstate: 0xCA4D74, deathflag: 0xD52800, tickcounter: 0x14B5DE4, graphics: 0xCC74A8, cinflags: 0x15186A0, coords: 0xD523A8, fade: 0x14BD450 },
+        h3: H3Offsets { levelname: 0x1E0D358, theatertime: 0x1EDAA9C, tickcounter: 0x2A1F34C, bspstate: 0x9A4BA0, deathflag: 0x1D91E68, deathflag_offset: 0x1077D },
+        hr: HrOffsets { levelname: 0x2907107, bspstate: 0x3716270, deathflag: 0xEEF330, deathflag_offset: 0x594249 },
+        odst: OdstOffsets { levelname: 0x2020CA8, streets: 0x2116FD8, bspstate: 0x2F91A9C, deathflag: 0xF3020C },
+        h4: H4Offsets { levelname: 0x2836433, bspstate: 0x2472A88, bspstate_offset: None },
+    },
+    VersionOffsets {
+        version: 2904,
+        mcc: MccOffsets { menustate: 0x3F7BAAD, menu: 0x8, pause: 0x5, pgcr: 0x6, gameindicator: 0x3F7C380, igt_float: 0x3F7C33C, comptimerstate: 0x3F7C358, comptimer: 0x1A4 },
+        mcc_winstore: None,
+        fadescale: 0.067,
+        h1: H1Offsets {
+            tickcounter: 0x2B88764, igt: 0x2E7A354, bspstate: 0x1B661CC, globals: 0x2B611A0, map: 0x2D66A24, cinflags: 0x2E773D8, coords: 0x2D7313C, fade: 0x2E7F868,
+            checklist: H1Checklist { a10: 89028072, a30: 1083179843, a50: 2623582826, b30: 1895318681, b40: 1935970024, c10: 974037405, c20: 714510620, c40: 2859044941, d20: 1178559651, d40: 3253884125 },
+        },
+        h2: H2Offsets { levelname: 0xD4ABF8, igt: 0x147D9F0, bspstate: 0xCACD74, deathflag: 0xD5A5A0, tickcounter: 0x14BDBC4, graphics: 0xCCF280, cinflags: 0x1520498, coords: 0xD5A148, fade: 0x14C5228 },
+        h3: H3Offsets { levelname: 0x1E092E8, theatertime: 0x1E9B4BC, tickcounter: 0x29E194C, bspstate: 0x99FCA0, deathflag: 0x1D8DF48, deathflag_offset: 0x1073D },
+        hr: HrOffsets { levelname: 0x28A4C3F, bspstate: 0x3719E24, deathflag: 0x23CC7D8, deathflag_offset: 0x1F419 },
+        odst: OdstOffsets { levelname: 0x202EA58, streets: 0x21353D8, bspstate: 0x2F9FD4C, deathflag: 0xF3EB8C },
+        h4: H4Offsets { levelname: 0x29A3743, bspstate: 0x25DC188, bspstate_offset: None },
+    },
+    VersionOffsets {
+        version: 2969,
+        mcc: MccOffsets { menustate: 0x3F9446C, menu: 0x11, pause: 0xB, pgcr: 0xC, gameindicator: 0x3F94E90, igt_float: 0x3F94F88, comptimerstate: 0x3F94F60, comptimer: 0x1A4 },
+        mcc_winstore: None,
+        fadescale: 0.067,
+        h1: H1Offsets {
+            tickcounter: 0x2CECFD4, igt: 0x14872C0, bspstate: 0x1CE4920, globals: 0x2CC5860, map: 0x2EEB024, cinflags: 0x2FFBD28, coords: 0x1DF5FF8, fade: 0x30041A8,
+            checklist: H1Checklist { a10: 2023477633, a30: 1197744442, a50: 522123179, b30: 2022995318, b40: 4112928798, c10: 4250424451, c20: 1165450382, c40: 2733116763, d20: 1722772470, d40: 3775314541 },
+        },
+        h2: H2Offsets { levelname: 0xD54498, igt: 0x14872C0, bspstate: 0xCB2D74, deathflag: 0xD63E80, tickcounter: 0x14C7494, graphics: 0xCD8998, cinflags: 0x14D9448, coords: 0xD63A28, fade: 0x14CEB68 },
+        h3: H3Offsets { levelname: 0x1EABB78, theatertime: 0x1F3DD5C, tickcounter: 0x2B4178C, bspstate: 0xA41D20, deathflag: 0x1E30758, deathflag_offset: 0x1074D },
+        hr: HrOffsets { levelname: 0x2A39A8F, bspstate: 0x3BB32A0, deathflag: 0x2514A88, deathflag_offset: 0x1F419 },
+        odst: OdstOffsets { levelname: 0x20D68F8, streets: 0x21DD308, bspstate: 0x3417D4C, deathflag: 0xFB940C },
+        h4: H4Offsets { levelname: 0x2B03887, bspstate: 0x27564B0, bspstate_offset: None },
+    },
+    VersionOffsets {
+        version: 3073,
+        mcc: MccOffsets { menustate: 0x401B76C, menu: 0x11, pause: 0xB, pgcr: 0xC, gameindicator: 0x401C1C0, igt_float: 0x401C204, comptimerstate: 0x401C1D8, comptimer: 0x1AC },
+        mcc_winstore: None,
+        fadescale: 0.067,
+        h1: H1Offsets {
+            tickcounter: 0x2CEBD34, igt: 0x3008134, bspstate: 0x1CECDFC, globals: 0x2CA0780, map: 0x2C9F7C4, cinflags: 0x3005198, coords: 0x2F00954, fade: 0x300D678,
+            checklist: H1Checklist { a10: 3589325267, a30: 3649693672, a50: 1186687708, b30: 1551598635, b40: 1100623455, c10: 3494823778, c20: 2445460720, c40: 3759075146, d20: 3442848200, d40: 1751474532 },
+        },
+        // Unverified: fade is the same address as coords, as it has always been for this build.
+        // H2 fades on 3073 read the player position until the real address is found.
+        h2: H2Offsets { levelname: 0xE6ED78, igt: 0x15A1BA0, bspstate: 0xDF7D74, deathflag: 0xE7E760, tickcounter: 0x15E1D74, graphics: 0xE1F178, cinflags: 0x15F42B8, coords: 0xE7E308, fade: 0xE7E308 },
+        h3: H3Offsets { levelname: 0x1E92AB8, theatertime: 0x1F2084C, tickcounter: 0x2B34F2C, bspstate: 0xA39220, deathflag: 0x1E19C98, deathflag_offset: 0xFDCD },
+        hr: HrOffsets { levelname: 0x2A2F6D7, bspstate: 0x3B9C020, deathflag: 0x250B808, deathflag_offset: 0x1ED09 },
+        odst: OdstOffsets { levelname: 0x20C0DA8, streets: 0x21463B4, bspstate: 0x33FD0DC, deathflag: 0xFDEAFC },
+        h4: H4Offsets { levelname: 0x2AE485F, bspstate: 0x2746930, bspstate_offset: None },
+    },
+    VersionOffsets {
+        version: 3272,
+        mcc: MccOffsets { menustate: 0x3FFDAA4, menu: 0x11, pause: 0xB, pgcr: 0xC, gameindicator: 0x3FFE4D8, igt_float: 0x3FFE590, comptimerstate: 0x3FFE4F0, comptimer: 0x1AC },
+        mcc_winstore: Some(MccOffsets { menustate: 0x3E4C034, menu: 0x11, pause: 0xB, pgcr: 0xC, gameindicator: 0x3E4CA78, igt_float: 0x3E4CB30, comptimerstate: 0x3E4CA90, comptimer: 0x1AC }),
+        fadescale: 0.067,
+        h1: H1Offsets {
+            tickcounter: 0x2B6F5E4, igt: 0x2EA31C4, bspstate: 0x1B860A4, globals: 0x2B23700, map: 0x2B22744, cinflags: 0x2EA01F8, coords: 0x2D9B9C4, fade: 0x2EA8708,
+            checklist: H1Checklist { a10: 1731967100, a30: 2334900663, a50: 2345488806, b30: 389775619, b40: 232036917, c10: 3544120777, c20: 2188406812, c40: 687169669, d20: 485256620, d40: 1783204841 },
+        },
+        h2: H2Offsets { levelname: 0xE6FE68, igt: 0x15A2EA0, bspstate: 0xDF8D74, deathflag: 0xE7FA50, tickcounter: 0x15E3074, graphics: 0xE20278, cinflags: 0x15F5788, coords: 0xE7F5E8, fade: 0x15EA778 },
+        h3: H3Offsets { levelname: 0x20A8118, theatertime: 0x2135F70, tickcounter: 0x2D3C04C, bspstate: 0xA4E170, deathflag: 0x202F2D8, deathflag_offset: 0xFDCD },
+        hr: HrOffsets { levelname: 0x2A1F587, bspstate: 0x4E2FBA8, deathflag: 0x24FB708, deathflag_offset: 0x1ED09 },
+        odst: OdstOffsets { levelname: 0x20EF128, streets: 0x21F05F8, bspstate: 0x46E261C, deathflag: 0x100CB3C },
+        h4: H4Offsets { levelname: 0x2AFF81F, bspstate: 0x275D550, bspstate_offset: None },
+    },
+    VersionOffsets {
+        version: 3385,
+        mcc: MccOffsets { menustate: 0x3FFCA94, menu: 0x11, pause: 0xB, pgcr: 0xC, gameindicator: 0x3FFD4C8, igt_float: 0x3FFD588, comptimerstate: 0x3FFD4E0, comptimer: 0x1AC },
+        mcc_winstore: Some(MccOffsets { menustate: 0x3E4B034, menu: 0x11, pause: 0xB, pgcr: 0xC, gameindicator: 0x3E4BA68, igt_float: 0x3E4BB28, comptimerstate: 0x3E4BA80, comptimer: 0x1AC }),
+        fadescale: 0.067,
+        h1: H1Offsets {
+            tickcounter: 0x2B6F5E4, igt: 0x2EA31D4, bspstate: 0x1B860A4, globals: 0x2B23700, map: 0x2B22744, cinflags: 0x2EA0208, coords: 0x2D9B9C4, fade: 0x2EA8718,
+            checklist: H1Checklist { a10: 1731967100, a30: 2334900663, a50: 2345488806, b30: 389775619, b40: 232036917, c10: 3544120777, c20: 2188406812, c40: 687169669, d20: 485256620, d40: 1783204841 },
+        },
+        h2: H2Offsets { levelname: 0xE6FE68, igt: 0x15A2EA0, bspstate: 0xDF8D74, deathflag: 0xE7FA50, tickcounter: 0x15E3074, graphics: 0xE20278, cinflags: 0x15F5788, coords: 0xE7F5E8, fade: 0x15EA778 },
+        h3: H3Offsets { levelname: 0x20A8118, theatertime: 0x2135F70, tickcounter: 0x2D3C04C, bspstate: 0xA4E170, deathflag: 0x202F2D8, deathflag_offset: 0xFDCD },
+        hr: HrOffsets { levelname: 0x2A1F587, bspstate: 0x4E2FBA8, deathflag: 0x24FB708, deathflag_offset: 0x1ED09 },
+        odst: OdstOffsets { levelname: 0x20EF128, streets: 0x21F05F8, bspstate: 0x46E261C, deathflag: 0x100CB3C },
+        h4: H4Offsets { levelname: 0x2AFF81F, bspstate: 0x275D550, bspstate_offset: None },
+    },
+    VersionOffsets {
+        version: 3528,
+        mcc: MccOffsets { menustate: 0x4000B8C, menu: 0x11, pause: 0xB, pgcr: 0xC, gameindicator: 0x4001658, igt_float: 0x4001644, comptimerstate: 0x4001698, comptimer: 0x1AC },
+        mcc_winstore: Some(MccOffsets { menustate: 0x3E4EFE4, menu: 0x11, pause: 0xB, pgcr: 0xC, gameindicator: 0x3E4FAB8, igt_float: 0x3E4FAA4, comptimerstate: 0x3E4FAF8, comptimer: 0x1AC }),
+        fadescale: 0.067,
+        h1: H1Offsets {
+            tickcounter: 0x2B6F5E4, igt: 0x2EA31D4, bspstate: 0x1B860A4, globals: 0x2B23700, map: 0x2B22744, cinflags: 0x2EA0208, coords: 0x2D9B9C4, fade: 0x2EA8718,
+            checklist: H1Checklist { a10: 1731967100, a30: 2334900663, a50: 2345488806, b30: 389775619, b40: 232036917, c10: 3544120777, c20: 2188406812, c40: 687169669, d20: 485256620, d40: 1783204841 },
+        },
+        h2: H2Offsets { levelname: 0xE70E68, igt: 0x15A3EA0, bspstate: 0xDF9D74, deathflag: 0xE80A50, tickcounter: 0x15E4074, graphics: 0xE21278, cinflags: 0x15F6788, coords: 0xE805E8, fade: 0x15EB778 },
+        h3: H3Offsets { levelname: 0x20A9118, theatertime: 0x2136F70, tickcounter: 0x2D3D04C, bspstate: 0xA4F170, deathflag: 0x20302D8, deathflag_offset: 0xFDCD },
+        hr: HrOffsets { levelname: 0x2A1F527, bspstate: 0x4E2FB28, deathflag: 0x24FB5F0, deathflag_offset: 0x1ED09 },
+        odst: OdstOffsets { levelname: 0x20EF128, streets: 0x21F05F8, bspstate: 0x46E261C, deathflag: 0x100CB3C },
+        h4: H4Offsets { levelname: 0x2AFF89F, bspstate: 0x275D5D0, bspstate_offset: None },
+    },
+];
@@ -0,0 +1,281 @@
+use crate::offsets::VersionOffsets;
+use crate::GameDLLs;
+use alloc::{collections::BTreeMap, vec};
+use asr::{signature::Signature, Address, Process};
+
+// Signature scanning for MCC builds the offset table doesn't know. The table entry for the
+// latest known build is used as a starting point and every global found by a signature
+// replaces the offset from the table.
+
+const CHUNK_SIZE: usize = 0x10000;
+
+// Raw access to the loaded modules, so the scanner can run against synthetic module images
+// in tests.
+pub trait ModuleMemory {
+    fn module_range(&self, name: &str) -> Option<(Address, u64)>;
+    fn read_bytes(&self, address: Address, buf: &mut [u8]) -> bool;
+}
+
+impl ModuleMemory for Process {
+    fn module_range(&self, name: &str) -> Option<(Address, u64)> {
+        self.get_module_range(name).ok()
+    }
+
+    fn read_bytes(&self, address: Address, buf: &mut [u8]) -> bool {
+        self.read_into_buf(address, buf).is_ok()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Module {
+    Exe,
+    Halo1,
+    Halo2,
+    Halo3,
+    Halo4,
+    Odst,
+    Reach,
+}
+
+impl Module {
+    pub const ALL: [Module; 7] = [
+        Module::Exe,
+        Module::Halo1,
+        Module::Halo2,
+        Module::Halo3,
+        Module::Halo4,
+        Module::Odst,
+        Module::Reach,
+    ];
+
+    // The exe has a different name on the WinStore, so it's passed in.
+    pub fn name(self, exe_name: &'static str) -> &'static str {
+        match self {
+            Module::Exe => exe_name,
+            Module::Halo1 => "halo1.dll",
+            Module::Halo2 => "halo2.dll",
+            Module::Halo3 => "halo3.dll",
+            Module::Halo4 => "halo4.dll",
+            Module::Odst => "halo3odst.dll",
+            Module::Reach => "haloreach.dll",
+        }
+    }
+
+    pub fn base(self, dlls: &GameDLLs) -> Address {
+        match self {
+            Module::Exe => dlls.exe_mcc,
+            Module::Halo1 => dlls.dll_halo1,
+            Module::Halo2 => dlls.dll_halo2,
+            Module::Halo3 => dlls.dll_halo3,
+            Module::Halo4 => dlls.dll_halo4,
+            Module::Odst => dlls.dll_halo3_odst,
+            Module::Reach => dlls.dll_halo_reach,
+        }
+    }
+}
+
+// The globals that can be found by signature. Each one maps to an offset in VersionOffsets.
+// Most aren't referenced until SIGNATURES is filled in.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Global {
+    MccMenuState,
+    H1Map,
+    H1TickCounter,
+    H1BspState,
+    H2LevelName,
+    H2TickCounter,
+    H2BspState,
+    H3LevelName,
+    H3TickCounter,
+    H3BspState,
+    HrLevelName,
+    HrBspState,
+    OdstLevelName,
+    OdstBspState,
+    H4LevelName,
+    H4BspState,
+}
+
+impl Global {
+    pub const ALL: [Global; 16] = [
+        Global::MccMenuState,
+        Global::H1Map,
+        Global::H1TickCounter,
+        Global::H1BspState,
+        Global::H2LevelName,
+        Global::H2TickCounter,
+        Global::H2BspState,
+        Global::H3LevelName,
+        Global::H3TickCounter,
+        Global::H3BspState,
+        Global::HrLevelName,
+        Global::HrBspState,
+        Global::OdstLevelName,
+        Global::OdstBspState,
+        Global::H4LevelName,
+        Global::H4BspState,
+    ];
+
+    pub fn module(self) -> Module {
+        match self {
+            Global::MccMenuState => Module::Exe,
+            Global::H1Map | Global::H1TickCounter | Global::H1BspState => Module::Halo1,
+            Global::H2LevelName | Global::H2TickCounter | Global::H2BspState => Module::Halo2,
+            Global::H3LevelName | Global::H3TickCounter | Global::H3BspState => Module::Halo3,
+            Global::HrLevelName | Global::HrBspState => Module::Reach,
+            Global::OdstLevelName | Global::OdstBspState => Module::Odst,
+            Global::H4LevelName | Global::H4BspState => Module::Halo4,
+        }
+    }
+
+    fn apply(self, offsets: &mut VersionOffsets, offset: u64) {
+        match self {
+            Global::MccMenuState => {
+                offsets.mcc.menustate = offset;
+                if let Some(mcc) = offsets.mcc_winstore.as_mut() {
+                    mcc.menustate = offset;
+                }
+            }
+            Global::H1Map => offsets.h1.map = offset,
+            Global::H1TickCounter => offsets.h1.tickcounter = offset,
+            Global::H1BspState => offsets.h1.bspstate = offset,
+            Global::H2LevelName => offsets.h2.levelname = offset,
+            Global::H2TickCounter => offsets.h2.tickcounter = offset,
+            Global::H2BspState => offsets.h2.bspstate = offset,
+            Global::H3LevelName => offsets.h3.levelname = offset,
+            Global::H3TickCounter => offsets.h3.tickcounter = offset,
+            Global::H3BspState => offsets.h3.bspstate = offset,
+            Global::HrLevelName => offsets.hr.levelname = offset,
+            Global::HrBspState => offsets.hr.bspstate = offset,
+            Global::OdstLevelName => offsets.odst.levelname = offset,
+            Global::OdstBspState => offsets.odst.bspstate = offset,
+            Global::H4LevelName => offsets.h4.levelname = offset,
+            Global::H4BspState => offsets.h4.bspstate = offset,
+        }
+    }
+}
+
+// Finds a global and returns its offset from the module base.
+pub trait GlobalScan: Sync {
+    fn scan(&self, memory: &dyn ModuleMemory, range: (Address, u64)) -> Option<u64>;
+}
+
+// A global referenced by a RIP-relative instruction, e.g. `mov eax, [rip+disp32]`. The
+// signature matches the instruction, `disp` is the position of the displacement in the
+// match and `next` is where the following instruction starts.
+#[allow(dead_code)]
+pub struct RipRelative<const N: usize> {
+    pub signature: Signature<N>,
+    pub disp: u64,
+    pub next: u64,
+}
+
+impl<const N: usize> GlobalScan for RipRelative<N> {
+    fn scan(&self, memory: &dyn ModuleMemory, range: (Address, u64)) -> Option<u64> {
+        let (base, size) = range;
+        let hit = scan_range(&self.signature, memory, range)?;
+        let mut disp = [0u8; 4];
+        if !memory.read_bytes(hit + self.disp, &mut disp) {
+            return None;
+        }
+        let target = (hit.value() + self.next).wrapping_add_signed(i32::from_le_bytes(disp) as i64);
+        let offset = target.checked_sub(base.value())?;
+        (offset < size).then_some(offset)
+    }
+}
+
+// Scans the range in chunks that overlap by the signature length, so matches crossing a
+// chunk boundary are still found.
+#[allow(dead_code)]
+fn scan_range<const N: usize>(signature: &Signature<N>, memory: &dyn ModuleMemory, (base, size): (Address, u64)) -> Option<Address> {
+    let mut buf = vec![0u8; CHUNK_SIZE.max(2 * N)];
+    let step = (buf.len() - N + 1) as u64;
+    let mut start = 0;
+    while start < size {
+        let len = (size - start).min(buf.len() as u64) as usize;
+        if memory.read_bytes(base + start, &mut buf[..len]) {
+            if let Some(pos) = signature.scan_once(&buf[..len]) {
+                return Some(base + start + pos as u64);
+            }
+        }
+        start += step;
+    }
+    None
+}
+
+// Signatures for every global that can be scanned for.
+// TODO: No signatures have been extracted from the game binaries yet, add them here as
+// `(Global::H1TickCounter, &RipRelative { signature: Signature::new("..."), disp: 2, next: 6 })`.
+pub const SIGNATURES: &[(Global, &dyn GlobalScan)] = &[];
+
+// Scans one module for every global that lives in it.
+pub fn scan_module(memory: &dyn ModuleMemory, module: Module, range: (Address, u64), signatures: &[(Global, &dyn GlobalScan)]) -> BTreeMap<Global, u64> {
+    signatures
+        .iter()
+        .filter(|(global, _)| global.module() == module)
+        .filter_map(|(global, scan)| Some((*global, scan.scan(memory, range)?)))
+        .collect()
+}
+
+// Offsets found by signature, kept per module and rescanned whenever a module moves.
+#[derive(Default)]
+pub struct ScannedOffsets {
+    found: BTreeMap<Global, u64>,
+    scanned: BTreeMap<Module, Address>,
+}
+
+impl ScannedOffsets {
+    // Scans any module that was loaded or reloaded since the last call. Returns true if
+    // anything was rescanned.
+    pub fn update(&mut self, memory: &dyn ModuleMemory, exe_name: &'static str, dlls: &GameDLLs) -> bool {
+        let mut rescanned = false;
+        for module in Module::ALL {
+            let base = module.base(dlls);
+            if self.scanned.get(&module).copied().unwrap_or_default() == base {
+                continue;
+            }
+            self.found.retain(|global, _| global.module() != module);
+            if base.is_null() {
+                self.scanned.insert(module, base);
+                continue;
+            }
+            // A module can be in the DLL list before its range can be queried, so it's only
+            // marked scanned once the range was found.
+            let Some((_, size)) = memory.module_range(module.name(exe_name)) else {
+                continue;
+            };
+            self.scanned.insert(module, base);
+            self.found.extend(scan_module(memory, module, (base, size), SIGNATURES));
+            rescanned = true;
+        }
+        rescanned
+    }
+
+    #[cfg(test)]
+    pub fn insert(&mut self, global: Global, offset: u64) {
+        self.found.insert(global, offset);
+    }
+
+    pub fn len(&self) -> usize {
+        self.found.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.found.is_empty()
+    }
+
+    // The table offsets with every scanned global swapped in.
+    // Whether every global was found, so none of them is left guessed.
+    pub fn found_all(&self) -> bool {
+        Global::ALL.iter().all(|global| self.found.contains_key(global))
+    }
+
+    pub fn apply(&self, offsets: &VersionOffsets) -> VersionOffsets {
+        let mut offsets = offsets.clone();
+        for (global, offset) in &self.found {
+            global.apply(&mut offsets, *offset);
+        }
+        offsets
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Default, Clone)]
+use crate::level::{H1Level, H2Level, H3Level, H4Level, HrLevel, Level, OdstLevel};
+
+#[derive(Default, Clone, Copy)]
 pub struct H1Checklist {
     pub a10: u32,
     pub a30: u32,
@@ -13,157 +15,167 @@ pub struct H1Checklist {
 }
 
 impl H1Checklist {
-    fn get(&self, level: &str) -> Option<u32> {
+    pub fn get(&self, level: H1Level) -> u32 {
         match level {
-            "a10" => Some(self.a10),
-            "a30" => Some(self.a30),
-            "a50" => Some(self.a50),
-            "b30" => Some(self.b30),
-            "b40" => Some(self.b40),
-            "c10" => Some(self.c10),
-            "c20" => Some(self.c20),
-            "c40" => Some(self.c40),
-            "d20" => Some(self.d20),
-            "d40" => Some(self.d40),
-            _ => None,
+            H1Level::PillarOfAutumn => self.a10,
+            H1Level::Halo => self.a30,
+            H1Level::TruthAndReconciliation => self.a50,
+            H1Level::SilentCartographer => self.b30,
+            H1Level::AssaultOnTheControlRoom => self.b40,
+            H1Level::GuiltySpark => self.c10,
+            H1Level::Library => self.c20,
+            H1Level::TwoBetrayals => self.c40,
+            H1Level::Keyes => self.d20,
+            H1Level::Maw => self.d40,
         }
     }
 }
 
+// The BSP state of H3, H4, ODST and Reach is a bitmask with a bit set for every loaded BSP.
+// Splits in those games happen when a BSP of the level's table is loaded for the first time,
+// the BSPs loaded with the level itself don't count.
+pub enum BspSplits {
+    // Split on loading the first listed BSPs, and on unloading the second listed ones where
+    // a section only unloads BSPs
+    Only(&'static [u8], &'static [u8]),
+    // Split on every BSP but the listed ones
+    Except(&'static [u8]),
+}
+
+impl BspSplits {
+    pub fn contains(&self, bsp: u8) -> bool {
+        match self {
+            BspSplits::Only(bsps, _) => bsps.contains(&bsp),
+            BspSplits::Except(bsps) => !bsps.contains(&bsp),
+        }
+    }
 
-pub fn get_h1_bsp_list(level: &str) -> &'static [u8] {
+    pub fn contains_unload(&self, bsp: u8) -> bool {
+        matches!(self, BspSplits::Only(_, unloads) if unloads.contains(&bsp))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self, BspSplits::Only(bsps, unloads) if bsps.is_empty() && unloads.is_empty())
+    }
+}
+
+// Indices of the BSPs set in a BSP bitmask, lowest first.
+pub fn bsp_indices(mask: u64) -> impl Iterator<Item = u8> {
+    (0..64).filter(move |bsp| mask & (1 << bsp) != 0)
+}
+
+pub fn get_h1_bsp_list(level: H1Level) -> &'static [u8] {
     match level {
-        "a10" => &[1, 2, 3, 4, 5, 6],         // PoA
-        "a30" => &[1],                        // Halo
-        "a50" => &[1, 2, 3],                  // TnR
-        "b30" => &[1],                        // SC
-        "b40" => &[0, 1, 2, 4, 8, 9, 10, 11], // AotCR
-        "c10" => &[1, 3, 4, 5],               // 343
-        "c20" => &[1, 2, 3],                  // Library
-        "c40" => &[12, 10, 1, 9, 8, 6, 0, 5], // TB
-        "d20" => &[4, 3, 2],                  // Keyes
-        "d40" => &[1, 2, 3, 4, 5, 6, 7],      // Maw
-        _ => &[],
+        H1Level::PillarOfAutumn => &[1, 2, 3, 4, 5, 6],
+        H1Level::Halo => &[1],
+        H1Level::TruthAndReconciliation => &[1, 2, 3],
+        H1Level::SilentCartographer => &[1],
+        H1Level::AssaultOnTheControlRoom => &[0, 1, 2, 4, 8, 9, 10, 11],
+        H1Level::GuiltySpark => &[1, 3, 4, 5],
+        H1Level::Library => &[1, 2, 3],
+        H1Level::TwoBetrayals => &[12, 10, 1, 9, 8, 6, 0, 5],
+        H1Level::Keyes => &[4, 3, 2],
+        H1Level::Maw => &[1, 2, 3, 4, 5, 6, 7],
     }
 }
 
-pub fn get_h2_bsp_list(level: &str) -> &'static [u8] {
+pub fn get_h2_bsp_list(level: H2Level) -> &'static [u8] {
     match level {
-        "01a" => &[],              // Armory
-        "01b" => &[2, 0, 3],       // Cairo
-        "03a" => &[1, 2],          // OS
-        "03b" => &[1],             // Metro
-        "04a" => &[3, 0],          // Arby
-        "04b" => &[0, 2, 1, 5],    // Oracle
-        "05a" => &[1],             // DH
-        "05b" => &[1, 2],          // Regret
-        "06a" => &[1, 2],          // SI
-        "06b" => &[1, 2, 3],       // QZ
-        "07a" => &[1, 2, 3, 4, 5], // GM
-        "08a" => &[1, 0],          // Uprising
-        "07b" => &[1, 2, 4],       // HC
-        "08b" => &[0, 1, 3],       // TGJ
+        H2Level::Armory => &[],
+        H2Level::Cairo => &[2, 0, 3],
+        H2Level::Outskirts => &[1, 2],
+        H2Level::Metropolis => &[1],
+        H2Level::Arbiter => &[3, 0],
+        H2Level::Oracle => &[0, 2, 1, 5],
+        H2Level::DeltaHalo => &[1],
+        H2Level::Regret => &[1, 2],
+        H2Level::SacredIcon => &[1, 2],
+        H2Level::QuarantineZone => &[1, 2, 3],
+        H2Level::Gravemind => &[1, 2, 3, 4, 5],
+        H2Level::Uprising => &[1, 0],
+        H2Level::HighCharity => &[1, 2, 4],
+        H2Level::GreatJourney => &[0, 1, 3],
         _ => &[],
     }
 }
 
-pub fn get_h3_bsp_list(level: &str) -> &'static [u64] {
+// Built from the first loads and unloads between the masks of recorded runs. The last section
+// of Crow's Nest and The Ark only swaps back to BSPs loaded earlier in the level, which a first
+// load or unload can't tell apart, so neither splits there.
+pub fn get_h3_bsp_splits(level: H3Level) -> BspSplits {
     match level {
-        "010" => &[7, 4111, 4127, 8589938751, 12884907135, 4294972543, 4294972927, 6143],
-        "020" => &[
-            2753726871765283,
-            351925325267239,
-            527984624664871,
-            527980329698111,
-            355107896034111,
-            495845384389503,
-            1058778157941759,
-            2081384101315583,
-            2076028277097471,
-            2043042928264191,
-        ],
-        "030" => &[708669603847, 1812476198927, 1709396983839, 128849018943, 2327872274495],
-        "040" => &[
-            70746701299715,
-            76347338653703,
-            5987184410895,
-            43920335569183,
-            52712133624127,
-            4449586119039,
-            110002702385663,
-            127560528691711,
-        ],
-        "050" => &[137438953607, 154618822791, 167503724703, 98784247967, 98784247999, 133143986431, 111669150207],
-        "070" => &[
-            319187993615142919,
-            497073530286903311,
-            5109160733019475999,
-            7059113264503853119,
-            7058267740062093439,
-            5296235395170702591,
-            6467180094380056063,
-            6471685893030682623,
-            6453663797939806207,
-        ],
-        "100" => &[
-            4508347378708774919,
-            2060429875000377375,
-            4384271889560765215,
-            2060429875000378143,
-            4508347378708775711,
-            4229124150272197439,
-            4105313024951190527,
-            4159567262287660031,
-            4153434048988972031,
-            4099400491367139327,
-            21673629041340192,
-        ],
-        "110" => &[4294967459, 4294967527, 4294967535, 4294967551],
-        "120" => &[1030792151055, 691489734703, 1924145349759, 1133871367679, 1202590844927, 1219770714111],
-        _ => &[],
+        H3Level::Sierra117 => BspSplits::Only(&[3, 4, 5, 6, 7, 8, 9, 10, 12, 32, 33], &[33]),
+        H3Level::CrowsNest => BspSplits::Only(&[2, 3, 4, 6, 7, 9, 32, 33, 34, 35, 36, 37, 38, 40, 41, 42, 45, 49, 50], &[40, 42]),
+        H3Level::TsavoHighway => BspSplits::Only(&[3, 4, 5, 33, 35, 36, 40, 41], &[]),
+        H3Level::Storm => BspSplits::Only(&[2, 3, 4, 5, 6, 7, 8, 32, 33, 34, 37, 39, 40, 41, 42, 43, 44, 45], &[]),
+        H3Level::Floodgate => BspSplits::Only(&[3, 4, 5, 6, 8, 32, 33, 34, 35, 36], &[]),
+        H3Level::Ark => BspSplits::Only(&[3, 4, 5, 6, 7, 8, 9, 10, 11, 32, 34, 36, 37, 42, 49, 52, 55, 56, 57, 59, 60, 61, 62], &[]),
+        H3Level::Covenant => BspSplits::Only(
+            &[3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 32, 36, 37, 38, 40, 45, 48, 49, 50, 51, 53, 54, 56],
+            &[51, 54],
+        ),
+        H3Level::Cortana => BspSplits::Only(&[2, 3, 4, 6], &[]),
+        H3Level::Halo => BspSplits::Only(&[4, 5, 6, 7, 8, 9, 10, 32, 34, 35, 40], &[]),
+        _ => BspSplits::Only(&[], &[]),
     }
 }
 
-pub fn get_h4_bsp_list(level: &str) -> &'static [u64] {
+// Halo 4 lists the BSPs that don't split, every other BSP does. The lists hold every BSP of
+// the masks that didn't split in recorded runs.
+pub fn get_h4_bsp_splits(level: H4Level) -> BspSplits {
     match level {
-        "m10" => &[0, 0x0000000001800000, 0x000000000700000F],
-        "m02" => &[0, 0x0000000080000C02],
-        "m30" => &[0, 0x0000000072001902],
-        "m40" => &[0, 0x00000040000C0001, 0x00000000013C0001],
-        "m60" => &[0, 0x0000C00002100001, 0x0000400006000001],
-        "m70" => &[0, 0x0000000100100004],
-        "m80" => &[0, 0x0020000080000006, 0x0000000080400006, 0x0000000180C0000E],
-        "m90" => &[0, 0x0000010000000006, 0x0000000000A00006],
-        _ => &[],
+        H4Level::Dawn => BspSplits::Except(&[0, 1, 2, 3, 23, 24, 25, 26]),
+        H4Level::Requiem => BspSplits::Except(&[1, 10, 11, 31]),
+        H4Level::Forerunner => BspSplits::Except(&[1, 8, 11, 12, 25, 28, 29, 30]),
+        H4Level::Infinity => BspSplits::Except(&[0, 18, 19, 20, 21, 24, 38]),
+        H4Level::Reclaimer => BspSplits::Except(&[0, 20, 25, 26, 46, 47]),
+        H4Level::Shutdown => BspSplits::Except(&[2, 20, 32]),
+        H4Level::Composer => BspSplits::Except(&[1, 2, 3, 22, 23, 31, 32, 53]),
+        H4Level::Midnight => BspSplits::Except(&[1, 2, 21, 23, 40]),
     }
 }
 
-pub fn get_hr_bsp_list(level: &str) -> &'static [u32] {
+// Built from the first loads between the masks of recorded runs.
+pub fn get_hr_bsp_splits(level: HrLevel) -> BspSplits {
     match level {
-        "m10" => &[143, 175, 239, 495],
-        "m20" => &[249, 505, 509, 511],
-        "m30" => &[269, 781, 797, 1821, 1853, 1917],
-        "m35" => &[4111, 4127, 4223, 4607, 5119],
-        "m45" => &[31, 383, 10111, 12159, 16255, 32639],
-        "m50" => &[5135, 5151, 5247, 5631, 8191],
-        "m52" => &[],
-        "m60" => &[113, 125, 4221, 4223, 5119],
-        "m70" => &[31, 63, 127, 255, 511, 1023, 2047],
-        _ => &[],
+        HrLevel::WinterContingency => BspSplits::Only(&[5, 6, 8], &[]),
+        HrLevel::OniSwordBase => BspSplits::Only(&[1, 2, 8], &[]),
+        HrLevel::Nightfall => BspSplits::Only(&[4, 5, 6, 9, 10], &[]),
+        HrLevel::TipOfTheSpear => BspSplits::Only(&[4, 5, 6, 7, 8, 9], &[]),
+        HrLevel::LongNightOfSolace => BspSplits::Only(&[5, 6, 8, 9, 10, 11, 12, 13, 14], &[]),
+        HrLevel::Exodus => BspSplits::Only(&[4, 5, 6, 7, 8, 9, 11], &[]),
+        HrLevel::NewAlexandria => BspSplits::Only(&[], &[]),
+        HrLevel::Package => BspSplits::Only(&[1, 2, 3, 7, 8, 9, 12], &[]),
+        HrLevel::PillarOfAutumn => BspSplits::Only(&[5, 6, 7, 8, 9, 10], &[]),
+        _ => BspSplits::Only(&[], &[]),
     }
 }
 
-pub fn get_odst_bsp_list(level: &str) -> &'static [u32] {
+// Built from the first loads and unloads between the masks of recorded runs. The runs start
+// after the level did, so every BSP of the first recorded mask is listed.
+pub fn get_odst_bsp_splits(level: OdstLevel) -> BspSplits {
     match level {
-        "h100" => &[296, 352, 304, 400, 896, 262, 388, 259],
-        "sc10" => &[14, 13, 9],
-        "sc11" => &[79, 92, 96],
-        "sc13" => &[11, 3, 7],
-        "sc12" => &[11, 14, 12],
-        "sc14" => &[11, 14, 12],
-        "sc15" => &[14, 28, 24],
-        "l200" => &[14, 28, 24, 48, 208, 224, 416],
-        "l300" => &[33, 41, 56, 112],
-        _ => &[],
+        OdstLevel::MombasaStreets => BspSplits::Only(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], &[1]),
+        OdstLevel::TayariPlaza => BspSplits::Only(&[0, 1, 2, 3], &[2]),
+        OdstLevel::UpliftReserve => BspSplits::Only(&[0, 1, 2, 3, 4, 5, 6], &[]),
+        OdstLevel::KizingoBoulevard => BspSplits::Only(&[0, 1, 2, 3], &[3]),
+        OdstLevel::OniAlphaSite => BspSplits::Only(&[0, 1, 2, 3], &[1]),
+        OdstLevel::NmpdHq => BspSplits::Only(&[0, 1, 2, 3], &[1]),
+        OdstLevel::KikowaniStation => BspSplits::Only(&[1, 2, 3, 4], &[2]),
+        OdstLevel::DataHive => BspSplits::Only(&[1, 2, 3, 4, 5, 6, 7, 8], &[2, 4]),
+        OdstLevel::CoastalHighway => BspSplits::Only(&[0, 3, 4, 5, 6], &[]),
+        _ => BspSplits::Only(&[], &[]),
+    }
+}
+
+// Whether BSP mode has anything to split on in the level.
+pub fn has_bsp_splits(level: Level) -> bool {
+    match level {
+        Level::Halo1(level) => !get_h1_bsp_list(level).is_empty(),
+        Level::Halo2(level) => !get_h2_bsp_list(level).is_empty(),
+        Level::Halo3(level) => !get_h3_bsp_splits(level).is_empty(),
+        Level::Halo4(level) => !get_h4_bsp_splits(level).is_empty(),
+        Level::Odst(level) => !get_odst_bsp_splits(level).is_empty(),
+        Level::Reach(level) => !get_hr_bsp_splits(level).is_empty(),
     }
 }
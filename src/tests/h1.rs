@@ -0,0 +1,406 @@
+use super::*;
+
+const LEVELS: &[&str] = &["a10", "a30", "a50", "b30", "b40", "c10", "c20", "c40", "d20", "d40"];
+
+#[derive(Clone, Copy)]
+enum StartTrigger {
+    // Opening cinematic ends while the tick counter is inside (min, max).
+    Cinematic(u32, u32),
+    // Tick counter passes through the window [min, max).
+    TickWindow(u32, u32),
+    // Cutscene skip flag drops.
+    CutsceneSkip,
+}
+
+fn il_start_trigger(level: &str) -> StartTrigger {
+    match level {
+        "a10" => StartTrigger::Cinematic(280, u32::MAX),
+        "a30" => StartTrigger::TickWindow(182, 190),
+        "a50" => StartTrigger::Cinematic(30, 900),
+        "b30" => StartTrigger::Cinematic(30, 1060),
+        "b40" => StartTrigger::Cinematic(30, 950),
+        "c10" => StartTrigger::Cinematic(30, 700),
+        _ => StartTrigger::CutsceneSkip,
+    }
+}
+
+fn fire(s: &mut Scenario, trigger: StartTrigger, tickcounter: u32) {
+    s.set(|p| &p.h1_xpos, -60.0f32);
+    s.set(|p| &p.h1_tickcounter, tickcounter);
+    match trigger {
+        StartTrigger::Cinematic(..) => {
+            s.set(|p| &p.h1_cinematic, true);
+            s.tick();
+            s.set(|p| &p.h1_cinematic, false);
+            s.tick();
+        }
+        StartTrigger::TickWindow(..) => s.tick(),
+        StartTrigger::CutsceneSkip => {
+            s.set(|p| &p.h1_cutsceneskip, true);
+            s.tick();
+            s.set(|p| &p.h1_cutsceneskip, false);
+            s.tick();
+        }
+    }
+}
+
+fn start_il(s: &mut Scenario, level: &str) {
+    s.level(level);
+    let trigger = il_start_trigger(level);
+    let tickcounter = match trigger {
+        StartTrigger::Cinematic(min, _) | StartTrigger::TickWindow(min, _) => min + 1,
+        StartTrigger::CutsceneSkip => 1000,
+    };
+    fire(s, trigger, tickcounter);
+}
+
+pub fn start_fg(s: &mut Scenario) {
+    s.level("a10");
+    fire(s, StartTrigger::Cinematic(280, u32::MAX), 300);
+    assert!(s.running(), "full game run should start on PoA");
+}
+
+#[test]
+fn fg_starts_when_poa_cinematic_ends() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    start_fg(&mut s);
+    assert_eq!(s.timer.starts(), vec![1]);
+}
+
+#[test]
+fn fg_does_not_start_during_poa_opening() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    s.level("a10");
+    fire(&mut s, StartTrigger::Cinematic(280, u32::MAX), 200);
+    assert_eq!(s.starts(), 0);
+}
+
+#[test]
+fn fg_does_not_start_on_later_levels() {
+    for &level in &LEVELS[1..] {
+        let mut s = Scenario::new(MCCGame::Halo1);
+        start_il(&mut s, level);
+        assert_eq!(s.starts(), 0, "{level}");
+    }
+}
+
+#[test]
+fn il_starts_on_every_level() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Halo1).il();
+        start_il(&mut s, level);
+        assert_eq!(s.starts(), 1, "{level}");
+        assert_eq!(s.splitter.started_level.map(Level::code), Some(level));
+    }
+}
+
+#[test]
+fn il_does_not_start_outside_window() {
+    for &level in LEVELS {
+        let trigger = il_start_trigger(level);
+        let late = match trigger {
+            StartTrigger::Cinematic(_, max) | StartTrigger::TickWindow(_, max) if max != u32::MAX => max,
+            _ => continue,
+        };
+        let mut s = Scenario::new(MCCGame::Halo1).il();
+        s.level(level);
+        fire(&mut s, trigger, late);
+        assert_eq!(s.starts(), 0, "{level}");
+    }
+}
+
+#[test]
+fn custom_maps_only_start_with_any_start() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    s.level("lumoria_a");
+    fire(&mut s, StartTrigger::CutsceneSkip, 100);
+    assert_eq!(s.starts(), 0);
+
+    let mut s = Scenario::new(MCCGame::Halo1);
+    s.settings.any_start = true;
+    s.level("lumoria_a");
+    fire(&mut s, StartTrigger::CutsceneSkip, 100);
+    assert_eq!(s.starts(), 1);
+}
+
+#[test]
+fn custom_map_il_resets_only_on_its_own_map() {
+    let mut s = Scenario::new(MCCGame::Halo1).il();
+    s.settings.any_start = true;
+    s.level("lumoria_a");
+    fire(&mut s, StartTrigger::CutsceneSkip, 100);
+    assert_eq!(s.starts(), 1);
+
+    let revert = |s: &mut Scenario| {
+        s.set(|p| &p.h1_igt, 500u32);
+        s.tick();
+        s.set(|p| &p.h1_igt, 2u32);
+        s.tick();
+    };
+    s.level("lumoria_b");
+    revert(&mut s);
+    assert_eq!(s.resets(), 0);
+
+    s.level("lumoria_a");
+    revert(&mut s);
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_resets_on_poa_revert_to_start() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    start_fg(&mut s);
+    s.set(|p| &p.h1_igt, 500u32);
+    s.tick();
+    s.set(|p| &p.h1_igt, 2u32);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+    assert!(!s.running());
+}
+
+#[test]
+fn fg_resets_on_poa_restart_from_load() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    start_fg(&mut s);
+    s.load(1);
+    s.tick();
+    s.set(|p| &p.h1_tickcounter, 10u32);
+    s.load(0);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_does_not_reset_on_later_level_restart() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    start_fg(&mut s);
+    s.level("a30");
+    s.set(|p| &p.h1_igt, 500u32);
+    s.tick();
+    s.set(|p| &p.h1_igt, 2u32);
+    s.tick();
+    assert_eq!(s.resets(), 0);
+}
+
+#[test]
+fn il_resets_on_started_level_restart() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Halo1).il();
+        start_il(&mut s, level);
+        s.set(|p| &p.h1_igt, 500u32);
+        s.tick();
+        s.set(|p| &p.h1_igt, 2u32);
+        s.tick();
+        assert_eq!(s.resets(), 1, "{level}");
+    }
+}
+
+#[test]
+fn il_resets_on_main_menu() {
+    let mut s = Scenario::new(MCCGame::Halo1).il();
+    start_il(&mut s, "b30");
+    s.menu(0);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_splits_on_level_load() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    start_fg(&mut s);
+    s.tick();
+    s.load(1);
+    s.tick();
+    assert_eq!(s.timer.splits(), vec![3]);
+}
+
+#[derive(Clone, Copy)]
+enum EndEdge {
+    CutsceneSkip,
+    Cinematic,
+}
+
+struct IlEnd {
+    bsp: u8,
+    fadelength: u16,
+    xpos: f32,
+    edge: EndEdge,
+}
+
+fn il_end(level: &str) -> IlEnd {
+    let end = |bsp, fadelength, xpos, edge| IlEnd { bsp, fadelength, xpos, edge };
+    match level {
+        "a10" => end(6, 0, 0.0, EndEdge::CutsceneSkip),
+        "a30" => end(1, 0, 0.0, EndEdge::CutsceneSkip),
+        "a50" => end(3, 15, 0.0, EndEdge::CutsceneSkip),
+        "b30" => end(0, 0, 0.0, EndEdge::CutsceneSkip),
+        "b40" => end(2, 0, 0.0, EndEdge::CutsceneSkip),
+        "c10" => end(1, 0, 0.0, EndEdge::CutsceneSkip),
+        "c20" => end(0, 0, 0.0, EndEdge::Cinematic),
+        "c40" => end(0, 0, 0.0, EndEdge::CutsceneSkip),
+        "d20" => end(0, 30, 0.0, EndEdge::Cinematic),
+        "d40" => end(0, 0, 1500.0, EndEdge::Cinematic),
+        _ => unreachable!(),
+    }
+}
+
+fn play_il_end(s: &mut Scenario, level: &str) {
+    let end = il_end(level);
+    s.set(|p| &p.h1_bspstate, end.bsp);
+    s.set(|p| &p.h1_fadelength, end.fadelength);
+    s.set(|p| &p.h1_xpos, end.xpos);
+    s.set(|p| &p.h1_tickcounter, 5000u32);
+    s.set(|p| &p.h1_cinematic, false);
+    s.set(|p| &p.h1_cutsceneskip, false);
+    s.tick();
+    match end.edge {
+        EndEdge::CutsceneSkip => s.set(|p| &p.h1_cutsceneskip, true),
+        EndEdge::Cinematic => s.set(|p| &p.h1_cinematic, true),
+    }
+    s.tick();
+}
+
+#[test]
+fn il_end_split_on_every_level() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Halo1).il();
+        start_il(&mut s, level);
+        s.tick();
+        assert_eq!(s.splits(), 0, "{level}");
+        play_il_end(&mut s, level);
+        assert_eq!(s.splits(), 1, "{level}");
+    }
+}
+
+#[test]
+fn il_end_split_ignores_wrong_bsp() {
+    let mut s = Scenario::new(MCCGame::Halo1).il();
+    start_il(&mut s, "a10");
+    s.set(|p| &p.h1_bspstate, 2u8);
+    s.tick();
+    s.set(|p| &p.h1_cutsceneskip, true);
+    s.tick();
+    assert_eq!(s.splits(), 0);
+}
+
+#[test]
+fn maw_ending_does_not_split_on_death() {
+    let mut s = Scenario::new(MCCGame::Halo1).il();
+    start_il(&mut s, "d40");
+    s.set(|p| &p.h1_deathflag, true);
+    play_il_end(&mut s, "d40");
+    assert_eq!(s.splits(), 0);
+}
+
+fn stock_checksum(level: &str) -> u32 {
+    let level = H1Level::parse(level.as_bytes()).unwrap();
+    crate::offsets::for_version(MCC_VERSION).unwrap().h1.checklist.get(level)
+}
+
+#[test]
+fn map_valid_on_stock_maps() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    s.set(|p| &p.h1_checksum, stock_checksum("a10"));
+    start_fg(&mut s);
+    assert_eq!(s.timer.variable("Map Valid"), Some("Yes"));
+}
+
+#[test]
+fn map_check_skips_custom_maps_and_loads() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    s.level("lumoria_a");
+    s.set(|p| &p.h1_checksum, 1234u32);
+    s.tick();
+    assert_eq!(s.timer.variable("Map Valid"), Some("Unknown"));
+
+    // The checksum of the last map can still be around while the next one loads.
+    s.level("a30");
+    s.load(1);
+    s.tick();
+    assert_eq!(s.timer.variable("Map Valid"), Some("Unknown"));
+}
+
+#[test]
+fn altered_map_refuses_to_start_with_map_check() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    s.settings.h1_map_check = true;
+    s.set(|p| &p.h1_checksum, 1234u32);
+    s.level("a10");
+    fire(&mut s, StartTrigger::Cinematic(280, u32::MAX), 300);
+    assert_eq!(s.starts(), 0);
+    assert_eq!(s.timer.variable("Map Valid"), Some("No"));
+
+    s.set(|p| &p.h1_checksum, stock_checksum("a10"));
+    fire(&mut s, StartTrigger::Cinematic(280, u32::MAX), 300);
+    assert_eq!(s.starts(), 1);
+}
+
+#[test]
+fn altered_map_flags_the_run() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    s.set(|p| &p.h1_checksum, stock_checksum("a10"));
+    start_fg(&mut s);
+
+    s.level("a30");
+    s.set(|p| &p.h1_checksum, 1234u32);
+    s.tick();
+    assert_eq!(s.timer.variable("Map Valid"), Some("No"));
+    assert_eq!(s.splitter.h1_altered_map, Some(H1Level::Halo));
+
+    // Stays flagged on later stock maps until the run is reset.
+    s.level("a50");
+    s.set(|p| &p.h1_checksum, stock_checksum("a50"));
+    s.tick();
+    assert_eq!(s.timer.variable("Map Valid"), Some("No"));
+}
+
+// Plays for `seconds` of real time at 10 ticks per second, with the tick counter advancing
+// `ticks_per_second`.
+fn play_at_speed(s: &mut Scenario, mut tickcounter: u32, seconds: u32, ticks_per_second: u32) -> u32 {
+    s.timer.tick_length = Duration::milliseconds(100);
+    for _ in 0..seconds * 10 {
+        tickcounter += ticks_per_second / 10;
+        s.set(|p| &p.h1_tickcounter, tickcounter);
+        s.tick();
+    }
+    tickcounter
+}
+
+#[test]
+fn speed_check_passes_at_30_ticks_per_second() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    start_fg(&mut s);
+    play_at_speed(&mut s, 300, 10, 30);
+    assert_eq!(s.timer.variable("H1 Speed Valid"), Some("Yes"));
+    assert_eq!(s.timer.variable("Tick Drift"), Some("+0.00s"));
+}
+
+#[test]
+fn speed_check_fails_when_running_fast() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    start_fg(&mut s);
+    let tickcounter = play_at_speed(&mut s, 300, 5, 30);
+    let tickcounter = play_at_speed(&mut s, tickcounter, 6, 40);
+    assert_eq!(s.timer.variable("H1 Speed Valid"), Some("No"));
+    assert_eq!(s.timer.variable("Tick Drift"), Some("+1.70s"));
+
+    // Stays failed for the rest of the run.
+    play_at_speed(&mut s, tickcounter, 20, 30);
+    assert_eq!(s.timer.variable("H1 Speed Valid"), Some("No"));
+}
+
+#[test]
+fn speed_check_ignores_pauses() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    start_fg(&mut s);
+    let tickcounter = play_at_speed(&mut s, 300, 3, 30);
+
+    // Tick counter stands still on the pause screen while real time goes on.
+    s.set(|p| &p.mcc_pauseindicator, 1u8);
+    s.ticks(300);
+    s.set(|p| &p.mcc_pauseindicator, 0u8);
+
+    play_at_speed(&mut s, tickcounter, 10, 30);
+    assert_eq!(s.timer.variable("H1 Speed Valid"), Some("Yes"));
+    assert_eq!(s.timer.variable("Tick Drift"), Some("+0.00s"));
+}
@@ -0,0 +1,430 @@
+use super::*;
+
+const LEVELS: &[&str] = &["01a", "01b", "03a", "03b", "04a", "04b", "05a", "05b", "06a", "06b", "07a", "08a", "07b", "08b"];
+
+pub fn start_fg(s: &mut Scenario) {
+    s.level("01a");
+    s.set(|p| &p.h2_tickcounter, 20u32);
+    s.tick();
+    s.set(|p| &p.h2_tickcounter, 27u32);
+    s.tick();
+    assert!(s.running(), "full game run should start on The Armory");
+}
+
+fn start_il(s: &mut Scenario, level: &str) {
+    s.level(level);
+    if level == "01a" {
+        s.set(|p| &p.h2_tickcounter, 27u32);
+    } else {
+        s.set(|p| &p.h2_igt, 5u32);
+        s.tick();
+        s.set(|p| &p.h2_igt, 15u32);
+    }
+    s.tick();
+}
+
+#[test]
+fn fg_starts_in_armory_tick_window() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    start_fg(&mut s);
+    assert_eq!(s.timer.starts(), vec![1]);
+}
+
+#[test]
+fn fg_does_not_start_outside_armory_tick_window() {
+    for tickcounter in [25u32, 30] {
+        let mut s = Scenario::new(MCCGame::Halo2);
+        s.level("01a");
+        s.set(|p| &p.h2_tickcounter, tickcounter);
+        s.tick();
+        assert_eq!(s.starts(), 0, "{tickcounter}");
+    }
+}
+
+#[test]
+fn fg_starts_from_the_main_menu_at_full_tick_rate() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.menu(0);
+    s.tick();
+    assert_eq!(s.timer.tick_rate, MENU_TICK_RATE);
+
+    // Picking the mission loads before the level ticks, which raises the rate in time for
+    // the tick window.
+    s.load(1);
+    s.tick();
+    assert_eq!(s.timer.tick_rate, GAMEPLAY_TICK_RATE);
+    s.menu(1);
+    s.load(0);
+    start_fg(&mut s);
+    assert_eq!(s.timer.tick_rate, GAMEPLAY_TICK_RATE);
+}
+
+#[test]
+fn pausing_keeps_the_full_tick_rate() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.level("03a");
+    s.menu(1);
+    s.tick();
+    assert_eq!(s.timer.tick_rate, GAMEPLAY_TICK_RATE);
+
+    s.menu(0);
+    s.ticks(10);
+    assert_eq!(s.timer.tick_rate, GAMEPLAY_TICK_RATE);
+
+    s.menu(1);
+    s.tick();
+    assert_eq!(s.timer.tick_rate, GAMEPLAY_TICK_RATE);
+}
+
+#[test]
+fn unreadable_state_drops_to_the_menu_tick_rate() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.level("03a");
+    s.menu(1);
+    s.tick();
+    assert_eq!(s.timer.tick_rate, GAMEPLAY_TICK_RATE);
+
+    s.ptrs.mcc_menuindicator = DeepPtr::default();
+    s.tick();
+    assert_ne!(s.timer.variable("Splitter Status"), Some("OK"));
+    assert_eq!(s.timer.tick_rate, MENU_TICK_RATE);
+}
+
+#[test]
+fn fg_starts_on_cairo_fade_in() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.level("01b");
+    s.set(|p| &p.h2_tickcounter, 10u32);
+    s.set(|p| &p.h2_fadebyte, 1u8);
+    s.tick();
+    s.set(|p| &p.h2_fadebyte, 0u8);
+    s.tick();
+    assert_eq!(s.timer.starts(), vec![1]);
+    assert_eq!(s.splitter.started_level.map(Level::code), Some("01b"));
+}
+
+#[test]
+fn fg_does_not_start_on_later_levels() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.level("03b");
+    s.set(|p| &p.h2_tickcounter, 10u32);
+    s.set(|p| &p.h2_fadebyte, 1u8);
+    s.tick();
+    s.set(|p| &p.h2_fadebyte, 0u8);
+    s.set(|p| &p.h2_igt, 15u32);
+    s.tick();
+    assert_eq!(s.starts(), 0);
+}
+
+#[test]
+fn il_starts_on_every_level() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Halo2).il();
+        start_il(&mut s, level);
+        assert_eq!(s.starts(), 1, "{level}");
+        assert_eq!(s.splitter.started_level.map(Level::code), Some(level));
+    }
+}
+
+#[test]
+fn il_does_not_start_after_igt_window() {
+    let mut s = Scenario::new(MCCGame::Halo2).il();
+    s.level("05a");
+    s.set(|p| &p.h2_igt, 30u32);
+    s.tick();
+    assert_eq!(s.starts(), 0);
+}
+
+#[test]
+fn fg_resets_on_armory_revert() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    start_fg(&mut s);
+    s.set(|p| &p.h2_igt, 500u32);
+    s.tick();
+    s.set(|p| &p.h2_igt, 5u32);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_resets_on_armory_restart_from_load() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    start_fg(&mut s);
+    s.load(1);
+    s.tick();
+    s.set(|p| &p.h2_tickcounter, 10u32);
+    s.load(0);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_does_not_reset_on_later_level_revert() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    start_fg(&mut s);
+    s.level("03a");
+    s.set(|p| &p.h2_igt, 500u32);
+    s.tick();
+    s.set(|p| &p.h2_igt, 5u32);
+    s.tick();
+    assert_eq!(s.resets(), 0);
+}
+
+#[test]
+fn il_resets_on_started_level_restart() {
+    for &level in &LEVELS[1..] {
+        let mut s = Scenario::new(MCCGame::Halo2).il();
+        start_il(&mut s, level);
+        s.load(1);
+        s.set(|p| &p.h2_igt, 0u32);
+        s.tick();
+        assert_eq!(s.resets(), 1, "{level}");
+    }
+}
+
+#[test]
+fn il_does_not_reset_on_other_level() {
+    let mut s = Scenario::new(MCCGame::Halo2).il();
+    start_il(&mut s, "04a");
+    s.level("04b");
+    s.load(1);
+    s.set(|p| &p.h2_igt, 0u32);
+    s.tick();
+    assert_eq!(s.resets(), 0);
+}
+
+#[test]
+fn any_level_outskirts_starts_at_the_fixed_fade_scale() {
+    // The start keeps the 0.067 scale on every version, 2448's 0.183 included
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.settings.any_level = true;
+    s.ptrs.fadescale = 0.183;
+    s.level("03a");
+    s.set(|p| &p.h2_fadebyte, 1u8);
+    s.set(|p| &p.h2_fadetick, 10u32);
+    s.set(|p| &p.h2_fadelength, 300u16);
+    play(&mut s, 20, 9);
+    assert_eq!(s.starts(), 0);
+    play(&mut s, 29, 1);
+    assert_eq!(s.timer.starts(), vec![9]);
+}
+
+#[test]
+fn fg_splits_on_level_load() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    start_fg(&mut s);
+    s.level("03a");
+    s.tick();
+    s.load(1);
+    s.tick();
+    assert_eq!(s.timer.splits().first(), Some(&3));
+}
+
+// The Great Journey's BSP splits depend on the order BSPs are visited in and the position
+// the player crosses at, since the level revisits BSPs 0 and 1.
+fn tgj_bsp(s: &mut Scenario, bsp: u8, x: f32, y: f32) {
+    s.set(|p| &p.h2_xpos, x);
+    s.set(|p| &p.h2_ypos, y);
+    s.set(|p| &p.h2_bspstate, bsp);
+    s.tick();
+}
+
+fn start_tgj(s: &mut Scenario) {
+    s.settings.bsp_mode = true;
+    start_il(s, "08b");
+    assert!(s.running());
+}
+
+#[test]
+fn tgj_splits_on_each_bsp_crossing_once() {
+    let mut s = Scenario::new(MCCGame::Halo2).il();
+    start_tgj(&mut s);
+
+    tgj_bsp(&mut s, 1, 0.0, -20.0);
+    assert_eq!(s.splits(), 1);
+    tgj_bsp(&mut s, 0, -15.0, 25.0);
+    assert_eq!(s.splits(), 2);
+    tgj_bsp(&mut s, 1, 20.0, 20.0);
+    assert_eq!(s.splits(), 3);
+    tgj_bsp(&mut s, 0, 50.0, 0.0);
+    assert_eq!(s.splits(), 4);
+    tgj_bsp(&mut s, 3, 0.0, 0.0);
+    assert_eq!(s.splits(), 5);
+}
+
+#[test]
+fn tgj_ignores_backtracking() {
+    let mut s = Scenario::new(MCCGame::Halo2).il();
+    start_tgj(&mut s);
+
+    tgj_bsp(&mut s, 1, 0.0, -20.0);
+    tgj_bsp(&mut s, 0, 0.0, -20.0);
+    tgj_bsp(&mut s, 1, 0.0, -20.0);
+    assert_eq!(s.splits(), 1);
+}
+
+#[test]
+fn tgj_later_crossings_need_earlier_ones() {
+    let mut s = Scenario::new(MCCGame::Halo2).il();
+    start_tgj(&mut s);
+
+    // Positions of the third and fourth crossings, before the second happened.
+    tgj_bsp(&mut s, 1, 20.0, 20.0);
+    tgj_bsp(&mut s, 0, 50.0, 0.0);
+    assert_eq!(s.splits(), 0);
+}
+
+#[test]
+fn tgj_ready_flag_set_on_final_bsp() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    start_fg(&mut s);
+    s.level("08b");
+    s.tick();
+    assert!(!s.splitter.h2_tgj_ready_flag);
+
+    s.set(|p| &p.h2_tickcounter, 1000u32);
+    s.set(|p| &p.h2_bspstate, 3u8);
+    s.tick();
+    assert!(s.splitter.h2_tgj_ready_flag);
+    assert_eq!(s.splitter.h2_tgj_ready_time, 1000);
+
+    // Leaving the level clears it again.
+    s.level("01a");
+    s.tick();
+    assert!(!s.splitter.h2_tgj_ready_flag);
+}
+
+// Runs the tick counter forward one tick at a time.
+fn play(s: &mut Scenario, from: u32, ticks: u32) -> u32 {
+    for tick in 1..=ticks {
+        s.set(|p| &p.h2_tickcounter, from + tick);
+        s.tick();
+    }
+    from + ticks
+}
+
+// Fades out under the letterbox, as at the end of a level or in an internal cutscene.
+fn fade_out(s: &mut Scenario, from: u32) -> u32 {
+    s.set(|p| &p.h2_fadebyte, 1u8);
+    s.set(|p| &p.h2_letterbox, 0.5f32);
+    let tick = play(s, from, 1);
+    s.set(|p| &p.h2_letterbox, 0.97f32);
+    play(s, tick, 1)
+}
+
+// Ends the level and loads the next one, leaving the screen faded out.
+fn load_next_level(s: &mut Scenario, from: u32) {
+    fade_out(s, from);
+    assert_eq!(s.splitter.h2_load_state, H2LoadState::Playing);
+    s.set(|p| &p.h2_bspstate, 255u8);
+    s.tick();
+    s.load(1);
+    s.tick();
+    assert_eq!(s.splitter.h2_load_state, H2LoadState::Loading);
+
+    s.level("03a");
+    s.set(|p| &p.h2_tickcounter, 0u32);
+    s.set(|p| &p.h2_bspstate, 0u8);
+    s.set(|p| &p.h2_letterbox, 0.0f32);
+    s.load(0);
+    s.tick();
+    assert_eq!(s.splitter.h2_load_state, H2LoadState::FadeIn { faded_out: true });
+}
+
+#[test]
+fn fg_level_end_pauses_until_the_fade_in_finishes() {
+    // 300 fade length units are 20 ticks at the current fade scale and 54 on 2448.
+    for (fadescale, fade_ticks) in [(0.067, 20), (0.183, 54)] {
+        let mut s = Scenario::new(MCCGame::Halo2);
+        s.ptrs.fadescale = fadescale;
+        start_fg(&mut s);
+        let tick = play(&mut s, 27, 100);
+        assert!(!s.timer.game_time_paused);
+
+        load_next_level(&mut s, tick);
+        assert!(s.timer.game_time_paused);
+
+        s.set(|p| &p.h2_fadelength, 300u16);
+        play(&mut s, 0, fade_ticks - 1);
+        assert!(s.timer.game_time_paused, "{fadescale}");
+        play(&mut s, fade_ticks - 1, 1);
+        assert!(!s.timer.game_time_paused, "{fadescale}");
+    }
+}
+
+#[test]
+fn fg_internal_cutscene_fade_is_not_a_load() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    start_fg(&mut s);
+    let tick = play(&mut s, 27, 100);
+
+    let tick = fade_out(&mut s, tick);
+    assert_eq!(s.splitter.h2_load_state, H2LoadState::Playing);
+    let before = s.timer.game_time;
+
+    // Two seconds of cutscene under the fade, all of it timed
+    for tick in tick + 1..=tick + 120 {
+        s.set(|p| &p.h2_tickcounter, tick);
+        s.set(|p| &p.h2_igt, tick);
+        s.tick();
+        assert!(!s.timer.game_time_paused, "{tick}");
+    }
+    assert_eq!(s.splitter.h2_load_state, H2LoadState::Playing);
+
+    s.set(|p| &p.h2_fadebyte, 0u8);
+    play(&mut s, tick + 120, 1);
+    assert_eq!(s.splitter.h2_load_state, H2LoadState::Playing);
+    assert!(!s.timer.game_time_paused);
+    assert!(s.timer.game_time - before >= Duration::seconds(2), "{:?}", s.timer.game_time - before);
+}
+
+#[test]
+fn fg_pause_screen_does_not_end_fade_in() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    start_fg(&mut s);
+    let tick = play(&mut s, 27, 100);
+    load_next_level(&mut s, tick);
+
+    // Fade byte drops while paused on the first frame of the level.
+    s.set(|p| &p.mcc_pauseindicator, 1u8);
+    s.set(|p| &p.h2_fadebyte, 0u8);
+    s.ticks(10);
+    assert!(s.timer.game_time_paused);
+
+    s.set(|p| &p.mcc_pauseindicator, 0u8);
+    play(&mut s, 0, 1);
+    assert!(!s.timer.game_time_paused);
+}
+
+#[test]
+fn fg_frozen_tick_counter_after_load_does_not_resume() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    start_fg(&mut s);
+    let tick = play(&mut s, 27, 100);
+    load_next_level(&mut s, tick);
+
+    s.set(|p| &p.h2_fadebyte, 0u8);
+    s.set(|p| &p.h2_tickcounter, 500u32);
+    s.ticks(10);
+    assert!(s.timer.game_time_paused);
+
+    play(&mut s, 500, 1);
+    assert!(!s.timer.game_time_paused);
+}
+
+#[test]
+fn fg_bsp_255_is_a_load() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    start_fg(&mut s);
+    let tick = play(&mut s, 27, 100);
+
+    s.set(|p| &p.h2_bspstate, 255u8);
+    s.tick();
+    assert_eq!(s.splitter.h2_load_state, H2LoadState::Loading);
+    assert!(s.timer.game_time_paused);
+
+    s.set(|p| &p.h2_bspstate, 1u8);
+    s.tick();
+    play(&mut s, tick, 1);
+    assert!(!s.timer.game_time_paused);
+}
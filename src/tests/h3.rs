@@ -0,0 +1,347 @@
+use super::*;
+
+const LEVELS: &[&str] = &["010", "020", "030", "040", "050", "070", "100", "110", "120"];
+
+pub fn start_fg(s: &mut Scenario) {
+    s.level("010");
+    s.set(|p| &p.h3_theatertime, 10u32);
+    s.tick();
+    s.set(|p| &p.h3_theatertime, 20u32);
+    s.tick();
+    assert!(s.running(), "full game run should start on Sierra 117");
+}
+
+#[test]
+fn fg_starts_on_sierra_theater_time() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_fg(&mut s);
+    assert_eq!(s.timer.starts(), vec![1]);
+}
+
+#[test]
+fn fg_does_not_start_during_load() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    s.level("010");
+    s.load(1);
+    s.set(|p| &p.h3_theatertime, 20u32);
+    s.tick();
+    assert_eq!(s.starts(), 0);
+}
+
+#[test]
+fn fg_does_not_start_on_later_levels() {
+    for &level in &LEVELS[1..] {
+        let mut s = Scenario::new(MCCGame::Halo3);
+        s.level(level);
+        s.set(|p| &p.h3_theatertime, 20u32);
+        s.igt_start();
+        assert_eq!(s.starts(), 0, "{level}");
+    }
+}
+
+#[test]
+fn il_starts_on_every_level() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Halo3).il();
+        s.level(level);
+        s.igt_start();
+        assert_eq!(s.timer.starts(), vec![1], "{level}");
+        assert_eq!(s.splitter.started_level.map(Level::code), Some(level));
+    }
+}
+
+#[test]
+fn il_does_not_start_after_igt_window() {
+    let mut s = Scenario::new(MCCGame::Halo3).il();
+    s.level("030");
+    s.igt_float(0.5);
+    s.tick();
+    assert_eq!(s.starts(), 0);
+}
+
+#[test]
+fn fg_resets_on_sierra_restart() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_fg(&mut s);
+    s.set(|p| &p.h3_theatertime, 600u32);
+    s.tick();
+    s.set(|p| &p.h3_theatertime, 5u32);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_resets_on_arrival_load() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_fg(&mut s);
+    s.level("005");
+    s.load(1);
+    s.tick();
+    s.set(|p| &p.h3_tickcounter, 30u32);
+    s.load(0);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_does_not_reset_on_later_level_restart() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_fg(&mut s);
+    s.level("040");
+    s.set(|p| &p.h3_theatertime, 5u32);
+    s.tick();
+    assert_eq!(s.resets(), 0);
+}
+
+#[test]
+fn il_resets_on_started_level_revert() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Halo3).il();
+        s.level(level);
+        s.igt_start();
+        s.igt_advance(0.2, 60);
+        s.igt_float(0.1);
+        s.tick();
+        assert_eq!(s.resets(), 1, "{level}");
+    }
+}
+
+#[test]
+fn fg_splits_on_level_load() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_fg(&mut s);
+    s.tick();
+    s.load(1);
+    s.tick();
+    assert_eq!(s.timer.splits().first(), Some(&3));
+}
+
+#[test]
+fn il_end_splits_after_pgcr() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Halo3).il();
+        s.level(level);
+        s.igt_start();
+        s.igt_advance(0.2, 60);
+        s.pgcr(1);
+        s.tick();
+        assert_eq!(s.splits(), 0, "{level}");
+        s.tick();
+        assert_eq!(s.splits(), 1, "{level}");
+    }
+}
+
+fn start_coop(s: &mut Scenario) {
+    s.settings.h3_coop = true;
+    s.set(|p| &p.h3_tickcounter, 60u32);
+    start_fg(s);
+}
+
+fn advance_ticks(s: &mut Scenario, from: u32, ticks: u32) -> u32 {
+    for tick in 1..=ticks {
+        s.set(|p| &p.h3_tickcounter, from + tick);
+        s.tick();
+    }
+    from + ticks
+}
+
+#[test]
+fn coop_keeps_timing_through_pause_screen() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_coop(&mut s);
+    let tick = advance_ticks(&mut s, 60, 60);
+    let before = s.timer.game_time;
+
+    s.menu(0);
+    s.set(|p| &p.mcc_pauseindicator, 1u8);
+    advance_ticks(&mut s, tick, 120);
+    assert!(!s.timer.game_time_paused);
+    assert_eq!(s.timer.game_time - before, Duration::seconds(2));
+}
+
+#[test]
+fn coop_still_pauses_on_loads_and_main_menu() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_coop(&mut s);
+    advance_ticks(&mut s, 60, 60);
+
+    s.load(1);
+    s.tick();
+    assert!(s.timer.game_time_paused);
+    s.load(0);
+    s.tick();
+    assert!(!s.timer.game_time_paused);
+
+    s.menu(0);
+    s.tick();
+    assert!(s.timer.game_time_paused);
+}
+
+#[test]
+fn solo_pause_screen_pauses_game_time() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_fg(&mut s);
+    s.menu(0);
+    s.set(|p| &p.mcc_pauseindicator, 1u8);
+    s.tick();
+    assert!(s.timer.game_time_paused);
+}
+
+fn load_bsps(s: &mut Scenario, bsps: &[u8]) {
+    let mask = bsps.iter().fold(0u64, |mask, &bsp| mask | 1 << bsp);
+    s.set(|p| &p.h3_bspstate, mask);
+    s.tick();
+}
+
+#[test]
+fn bsp_splits_on_first_load_of_listed_bsp() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    s.settings.bsp_mode = true;
+    start_fg(&mut s);
+
+    load_bsps(&mut s, &[0, 1, 2]);
+    assert_eq!(s.splits(), 0);
+
+    load_bsps(&mut s, &[0, 1, 2, 3, 12]);
+    assert_eq!(s.splits(), 1);
+    // 11 isn't in the table, 4 is
+    load_bsps(&mut s, &[0, 1, 2, 3, 11, 12]);
+    assert_eq!(s.splits(), 1);
+    load_bsps(&mut s, &[0, 1, 2, 3, 4, 12]);
+    assert_eq!(s.splits(), 2);
+
+    // Going back to a BSP that was already loaded
+    load_bsps(&mut s, &[0, 1, 2, 3, 12]);
+    load_bsps(&mut s, &[0, 1, 2, 3, 4, 12]);
+    assert_eq!(s.splits(), 2);
+}
+
+#[test]
+fn bsps_loaded_with_the_level_do_not_split() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    s.settings.bsp_mode = true;
+    start_fg(&mut s);
+
+    // 3 is in the table of both levels
+    load_bsps(&mut s, &[0, 1, 2, 3]);
+    assert_eq!(s.splits(), 0);
+    s.level("020");
+    load_bsps(&mut s, &[2, 3]);
+    assert_eq!(s.splits(), 0);
+
+    // Loads and restarts bring the level's BSPs back in without splitting
+    s.load(1);
+    load_bsps(&mut s, &[]);
+    load_bsps(&mut s, &[2, 3, 4]);
+    s.load(0);
+    s.tick();
+    let splits = s.splits();
+    load_bsps(&mut s, &[2, 3, 4, 6]);
+    assert_eq!(s.splits(), splits + 1);
+}
+
+#[test]
+fn bsp_splits_on_first_unload_of_listed_bsp() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    s.settings.bsp_mode = true;
+    start_fg(&mut s);
+
+    load_bsps(&mut s, &[0, 1, 2]);
+    load_bsps(&mut s, &[0, 1, 2, 3, 4, 5, 6, 10, 12, 32, 33]);
+    assert_eq!(s.splits(), 1);
+    load_bsps(&mut s, &[0, 1, 2, 3, 4, 5, 6, 10, 12, 32]);
+    assert_eq!(s.splits(), 2);
+
+    load_bsps(&mut s, &[0, 1, 2, 3, 4, 5, 6, 10, 12, 32, 33]);
+    load_bsps(&mut s, &[0, 1, 2, 3, 4, 5, 6, 10, 12, 32]);
+    assert_eq!(s.splits(), 2);
+}
+
+#[test]
+fn bsp_splits_once_for_bsps_loaded_together() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    s.settings.bsp_mode = true;
+    start_fg(&mut s);
+
+    load_bsps(&mut s, &[0, 1, 2]);
+    load_bsps(&mut s, &[0, 1, 2, 4, 5]);
+    assert_eq!(s.splits(), 1);
+    load_bsps(&mut s, &[0, 1, 2, 5]);
+    load_bsps(&mut s, &[0, 1, 2, 4, 5]);
+    assert_eq!(s.splits(), 1);
+}
+
+#[test]
+fn bsp_cache_splits_on_every_load() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    s.settings.bsp_mode = true;
+    s.settings.bsp_cache = true;
+    start_fg(&mut s);
+
+    load_bsps(&mut s, &[0, 1, 2]);
+    load_bsps(&mut s, &[0, 1, 2, 3]);
+    load_bsps(&mut s, &[0, 1, 2]);
+    load_bsps(&mut s, &[0, 1, 2, 3]);
+    assert_eq!(s.splits(), 2);
+}
+
+#[test]
+fn bsp_splits_only_in_ticked_levels() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    s.settings.bsp_mode = true;
+    s.settings.bsp_levels.disabled = vec![H3Level::Sierra117.into()];
+    start_fg(&mut s);
+
+    load_bsps(&mut s, &[0, 1, 2]);
+    load_bsps(&mut s, &[0, 1, 2, 3]);
+    assert_eq!(s.splits(), 0);
+}
+
+fn die(s: &mut Scenario) {
+    s.set(|p| &p.h3_deathflag, true);
+    s.tick();
+    s.set(|p| &p.h3_deathflag, false);
+    s.tick();
+}
+
+#[test]
+fn counts_deaths() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_fg(&mut s);
+    die(&mut s);
+    die(&mut s);
+    assert_eq!(s.splitter.death_counter, 2);
+    assert_eq!(s.timer.variable("Deaths (Total)"), Some("2"));
+    assert_eq!(s.timer.variable("Deaths (Level)"), Some("2"));
+}
+
+#[test]
+fn counts_deaths_per_level() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_fg(&mut s);
+    die(&mut s);
+
+    s.level("020");
+    s.tick();
+    assert_eq!(s.timer.variable("Deaths (Level)"), Some("0"));
+    die(&mut s);
+    die(&mut s);
+    die(&mut s);
+
+    s.level("030");
+    s.tick();
+    assert_eq!(s.timer.variable("Deaths (Total)"), Some("4"));
+    assert_eq!(s.timer.variable("Deaths (Level)"), Some("0"));
+    assert_eq!(s.splitter.level_deaths, vec![(H3Level::Sierra117.into(), 1), (H3Level::CrowsNest.into(), 3), (H3Level::TsavoHighway.into(), 0)]);
+    assert_eq!(s.timer.variable("Deaths (History)"), Some("Sierra 117: 1, Crow's Nest: 3, Tsavo Highway: 0"));
+}
+
+#[test]
+fn death_history_clears_on_reset() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_fg(&mut s);
+    die(&mut s);
+    s.splitter.reset();
+    assert_eq!(s.splitter.death_counter, 0);
+    assert!(s.splitter.level_deaths.is_empty());
+}
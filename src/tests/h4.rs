@@ -0,0 +1,139 @@
+use super::*;
+
+const LEVELS: &[&str] = &["m10", "m02", "m30", "m40", "m60", "m70", "m80", "m90"];
+
+pub fn start_fg(s: &mut Scenario) {
+    s.level("m10");
+    s.igt_start();
+    assert!(s.running(), "full game run should start on Dawn");
+}
+
+#[test]
+fn fg_starts_on_dawn() {
+    let mut s = Scenario::new(MCCGame::Halo4);
+    start_fg(&mut s);
+    assert_eq!(s.timer.starts(), vec![1]);
+}
+
+#[test]
+fn fg_does_not_start_on_later_levels() {
+    for &level in &LEVELS[1..] {
+        let mut s = Scenario::new(MCCGame::Halo4);
+        s.level(level);
+        s.igt_start();
+        assert_eq!(s.starts(), 0, "{level}");
+    }
+}
+
+#[test]
+fn il_starts_on_every_level() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Halo4).il();
+        s.level(level);
+        s.igt_start();
+        assert_eq!(s.timer.starts(), vec![1], "{level}");
+    }
+}
+
+#[test]
+fn does_not_start_outside_igt_window() {
+    let mut s = Scenario::new(MCCGame::Halo4);
+    s.level("m10");
+    s.igt_float(0.1);
+    s.tick();
+    s.igt_float(0.5);
+    s.tick();
+    assert_eq!(s.starts(), 0);
+}
+
+#[test]
+fn fg_resets_on_dawn_revert() {
+    let mut s = Scenario::new(MCCGame::Halo4);
+    start_fg(&mut s);
+    s.igt_advance(0.2, 120);
+    s.igt_float(0.1);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_resets_on_dawn_reload() {
+    let mut s = Scenario::new(MCCGame::Halo4);
+    start_fg(&mut s);
+    s.igt_advance(0.2, 120);
+    s.load(1);
+    s.igt_float(0.0);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_does_not_reset_on_later_level_revert() {
+    let mut s = Scenario::new(MCCGame::Halo4);
+    start_fg(&mut s);
+    s.level("m30");
+    s.igt_advance(0.2, 120);
+    s.igt_float(0.1);
+    s.tick();
+    assert_eq!(s.resets(), 0);
+}
+
+#[test]
+fn il_resets_on_started_level_revert() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Halo4).il();
+        s.level(level);
+        s.igt_start();
+        s.igt_advance(0.2, 120);
+        s.igt_float(0.1);
+        s.tick();
+        assert_eq!(s.resets(), 1, "{level}");
+    }
+}
+
+#[test]
+fn splits_after_pgcr() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Halo4).il();
+        s.level(level);
+        s.igt_start();
+        s.igt_advance(0.2, 120);
+        s.pgcr(1);
+        s.ticks(2);
+        assert_eq!(s.splits(), 1, "{level}");
+    }
+}
+
+#[test]
+fn bsp_splits_on_bsps_missing_from_the_table() {
+    let mut s = Scenario::new(MCCGame::Halo4);
+    s.settings.bsp_mode = true;
+    start_fg(&mut s);
+
+    // Dawn lists the BSPs that don't split
+    s.set(|p| &p.h4_bspstate, 0b1111u64);
+    s.tick();
+    assert_eq!(s.splits(), 0);
+    s.set(|p| &p.h4_bspstate, 0b1111u64 | 1 << 4);
+    s.tick();
+    assert_eq!(s.splits(), 1);
+    s.set(|p| &p.h4_bspstate, 1u64 << 4);
+    s.tick();
+    assert_eq!(s.splits(), 1);
+}
+
+#[test]
+fn bsp_does_not_split_on_masks_excluded_from_the_table() {
+    let mut s = Scenario::new(MCCGame::Halo4);
+    s.settings.bsp_mode = true;
+    start_fg(&mut s);
+
+    s.set(|p| &p.h4_bspstate, 0b1111u64);
+    s.tick();
+    // Dawn's list holds every BSP of the masks that didn't split
+    for mask in [0x000000000700000Fu64, 0x0000000001800000] {
+        s.set(|p| &p.h4_bspstate, mask);
+        s.tick();
+    }
+    assert_eq!(s.splits(), 0);
+}
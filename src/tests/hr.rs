@@ -0,0 +1,94 @@
+use super::*;
+
+const LEVELS: &[&str] = &["m10", "m20", "m30", "m35", "m45", "m50", "m52", "m60", "m70"];
+
+pub fn start_fg(s: &mut Scenario) {
+    s.level("m10");
+    s.igt_start();
+    assert!(s.running(), "full game run should start on Winter Contingency");
+}
+
+#[test]
+fn fg_starts_on_winter_contingency() {
+    let mut s = Scenario::new(MCCGame::Reach);
+    start_fg(&mut s);
+    assert_eq!(s.timer.starts(), vec![1]);
+}
+
+#[test]
+fn fg_does_not_start_on_later_levels() {
+    for &level in &LEVELS[1..] {
+        let mut s = Scenario::new(MCCGame::Reach);
+        s.level(level);
+        s.igt_start();
+        assert_eq!(s.starts(), 0, "{level}");
+    }
+}
+
+#[test]
+fn il_starts_on_every_level() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Reach).il();
+        s.level(level);
+        s.igt_start();
+        assert_eq!(s.timer.starts(), vec![1], "{level}");
+    }
+}
+
+#[test]
+fn fg_resets_on_winter_contingency_revert() {
+    let mut s = Scenario::new(MCCGame::Reach);
+    start_fg(&mut s);
+    s.igt_advance(0.2, 120);
+    s.igt_float(0.1);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_resets_on_winter_contingency_reload() {
+    let mut s = Scenario::new(MCCGame::Reach);
+    start_fg(&mut s);
+    s.igt_advance(0.2, 120);
+    s.load(1);
+    s.igt_float(0.0);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_does_not_reset_on_later_level_revert() {
+    let mut s = Scenario::new(MCCGame::Reach);
+    start_fg(&mut s);
+    s.level("m20");
+    s.igt_advance(0.2, 120);
+    s.igt_float(0.1);
+    s.tick();
+    assert_eq!(s.resets(), 0);
+}
+
+#[test]
+fn il_resets_on_started_level_revert() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Reach).il();
+        s.level(level);
+        s.igt_start();
+        s.igt_advance(0.2, 120);
+        s.igt_float(0.1);
+        s.tick();
+        assert_eq!(s.resets(), 1, "{level}");
+    }
+}
+
+#[test]
+fn splits_after_pgcr() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::Reach).il();
+        s.level(level);
+        s.igt_start();
+        s.igt_advance(0.2, 120);
+        s.pgcr(1);
+        s.ticks(2);
+        assert_eq!(s.splits(), 1, "{level}");
+    }
+}
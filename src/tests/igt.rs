@@ -0,0 +1,139 @@
+use super::Scenario;
+use crate::igt::IgtAccumulator;
+use crate::timer::TimerCommand;
+use crate::MCCGame;
+use asr::time::Duration;
+
+// Feeds IGT values one tick at a time.
+fn run(igt: &mut IgtAccumulator, values: impl IntoIterator<Item = u32>, last: &mut u32) {
+    for value in values {
+        igt.update(value, *last, false);
+        *last = value;
+    }
+}
+
+#[test]
+fn pgcr_banks_the_level_rounded_down() {
+    let mut igt = IgtAccumulator::new(60);
+    let mut last = 0;
+    run(&mut igt, 0..=150, &mut last);
+    assert_eq!(igt.ticks(false), 150);
+
+    assert!(igt.pgcr(last));
+    assert_eq!(igt.ticks(false), 120);
+    assert_eq!(igt.levels_completed, 1);
+
+    // The load after the PGCR belongs to the same level.
+    assert!(!igt.load(last));
+    assert_eq!(igt.ticks(true), 120);
+    assert_eq!(igt.levels_completed, 1);
+}
+
+#[test]
+fn load_without_pgcr_banks_the_level() {
+    let mut igt = IgtAccumulator::new(30);
+    let mut last = 0;
+    run(&mut igt, 0..=100, &mut last);
+
+    assert!(igt.load(last));
+    assert_eq!(igt.ticks(true), 90);
+    assert_eq!(igt.time(true), Duration::seconds(3));
+    assert_eq!(igt.levels_completed, 1);
+}
+
+#[test]
+fn next_level_ignores_igt_left_over_from_the_last() {
+    let mut igt = IgtAccumulator::new(60);
+    let mut last = 0;
+    run(&mut igt, 0..=130, &mut last);
+    igt.load(last);
+
+    // IGT still reads the last level's value after the load, then starts over.
+    igt.update(last, last, false);
+    assert_eq!(igt.ticks(false), 120);
+    run(&mut igt, [0, 1, 2, 3], &mut last);
+    assert_eq!(igt.ticks(false), 123);
+}
+
+#[test]
+fn restart_rounds_to_the_nearest_second() {
+    for (played, banked) in [(89, 60), (91, 120), (120, 120)] {
+        let mut igt = IgtAccumulator::new(60);
+        let mut last = 0;
+        run(&mut igt, 0..=played, &mut last);
+        igt.restart(2, last, false);
+        assert_eq!(igt.ticks(false), banked + 2, "{played}");
+        assert_eq!(igt.restarts, 1);
+    }
+}
+
+#[test]
+fn restart_in_the_first_second_counts_as_one() {
+    let mut igt = IgtAccumulator::new(30);
+    let mut last = 0;
+    run(&mut igt, 0..=10, &mut last);
+    igt.restart(0, last, false);
+    assert_eq!(igt.ticks(false), 30);
+}
+
+#[test]
+fn exact_restart_keeps_every_tick() {
+    let mut igt = IgtAccumulator::new(60);
+    let mut last = 0;
+    run(&mut igt, 0..=91, &mut last);
+    igt.restart(0, last, true);
+    assert_eq!(igt.ticks(false), 91);
+}
+
+#[test]
+fn restart_before_the_level_counts_does_nothing() {
+    let mut igt = IgtAccumulator::new(60);
+    igt.load(500);
+    igt.restart(0, 500, false);
+    assert_eq!(igt.ticks(false), 0);
+    assert_eq!(igt.restarts, 0);
+}
+
+#[test]
+fn large_jumps_and_reverts_are_ignored() {
+    let mut igt = IgtAccumulator::new(60);
+    let mut last = 0;
+    run(&mut igt, [0, 60, 1000, 1001, 900, 901], &mut last);
+    assert_eq!(igt.ticks(false), 62);
+}
+
+#[test]
+fn converts_ticks_without_drift() {
+    let mut igt = IgtAccumulator::new(30);
+    let mut last = 0;
+    run(&mut igt, 0..=30 * 3600 + 1, &mut last);
+    assert_eq!(igt.time(false), Duration::hours(1) + Duration::milliseconds(33));
+}
+
+#[test]
+fn level_time_leaves_out_banked_levels() {
+    let mut igt = IgtAccumulator::new(60);
+    let mut last = 0;
+    run(&mut igt, 0..=150, &mut last);
+    igt.load(last);
+    igt.update(last, last, false);
+    run(&mut igt, 0..=30, &mut last);
+    assert_eq!(igt.level_time(), Duration::milliseconds(500));
+    assert_eq!(igt.time(false), Duration::milliseconds(2500));
+}
+
+#[test]
+fn publishes_igt_variables_on_change() {
+    let mut s = Scenario::new(MCCGame::Halo3).il();
+    s.level("010");
+    s.igt_start();
+    s.igt_advance(0.2, 60);
+    assert!(s.running());
+    let level = s.timer.variable("Level IGT").unwrap().to_string();
+    assert_eq!(s.timer.variable("Run IGT"), Some(level.as_str()));
+    assert!(level.starts_with("0:01."), "{level}");
+
+    let events = s.timer.events.len();
+    s.ticks(5);
+    assert!(!s.timer.events[events..].iter().any(|e| matches!(e.command, TimerCommand::SetVariable(..))));
+}
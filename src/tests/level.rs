@@ -0,0 +1,143 @@
+use super::*;
+
+#[test]
+fn parses_every_level_code() {
+    for &level in H1Level::ALL {
+        assert_eq!(H1Level::parse(level.code().as_bytes()), Some(level));
+    }
+    for &level in H2Level::ALL {
+        assert_eq!(H2Level::parse(level.code().as_bytes()), Some(level));
+    }
+    for &level in H3Level::ALL {
+        assert_eq!(H3Level::parse(level.code().as_bytes()), Some(level));
+    }
+    for &level in H4Level::ALL {
+        assert_eq!(H4Level::parse(level.code().as_bytes()), Some(level));
+    }
+    for &level in OdstLevel::ALL {
+        assert_eq!(OdstLevel::parse(level.code().as_bytes()), Some(level));
+    }
+    for &level in HrLevel::ALL {
+        assert_eq!(HrLevel::parse(level.code().as_bytes()), Some(level));
+    }
+}
+
+#[test]
+fn parses_the_level_name_watcher() {
+    assert_eq!(H2Level::parse(cstr::<3>("08b").as_bytes()), Some(H2Level::GreatJourney));
+    assert_eq!(OdstLevel::parse(cstr::<4>("sc13").as_bytes()), Some(OdstLevel::KizingoBoulevard));
+    assert_eq!(H1Level::parse(cstr::<32>("a10").as_bytes()), Some(H1Level::PillarOfAutumn));
+}
+
+#[test]
+fn other_maps_do_not_parse() {
+    assert_eq!(H1Level::parse(b""), None);
+    assert_eq!(H1Level::parse(b"lumoria_a"), None);
+    assert_eq!(H1Level::parse(b"a1"), None);
+    assert_eq!(H3Level::parse(b"m10"), None);
+}
+
+#[test]
+fn levels_know_their_game_and_name() {
+    let level = Level::from(HrLevel::Package);
+    assert_eq!(level.game(), MCCGame::Reach);
+    assert_eq!(level.code(), "m60");
+    assert_eq!(level.to_string(), "The Package");
+    assert_eq!(Level::from(OdstLevel::DataHive).game(), MCCGame::ODST);
+}
+
+#[test]
+fn missions_are_numbered_from_the_first() {
+    assert_eq!(H1Level::FIRST.number(), Some(1));
+    assert_eq!(H1Level::LAST.number(), Some(H1Level::MISSIONS));
+    assert_eq!(H1Level::MISSIONS, 10);
+    assert_eq!(H2Level::MISSIONS, 14);
+    assert_eq!(H3Level::MISSIONS, 9);
+    assert_eq!(H4Level::MISSIONS, 8);
+    assert_eq!(OdstLevel::MISSIONS, 9);
+    assert_eq!(HrLevel::MISSIONS, 9);
+
+    assert_eq!(H2Level::Metropolis.number(), Some(4));
+    assert_eq!(OdstLevel::KizingoBoulevard.number(), Some(4));
+}
+
+#[test]
+fn cutscene_levels_are_intros_or_outros() {
+    assert!(H2Level::Heretic.is_intro());
+    assert_eq!(H2Level::Heretic.number(), None);
+    assert!(H3Level::Arrival.is_intro());
+    assert!(H3Level::Epilogue.is_outro());
+    assert!(OdstLevel::PrepareToDrop.is_intro());
+    assert!(OdstLevel::Epilogue.is_outro());
+    assert!(HrLevel::NobleActual.is_intro());
+    assert!(H1Level::ALL.iter().all(|level| level.is_mission()));
+}
+
+#[test]
+fn next_mission_stops_at_the_last() {
+    assert_eq!(Level::from(H3Level::Arrival).next_mission(), Some(H3Level::FIRST.into()));
+    assert_eq!(Level::from(H2Level::HighCharity).next_mission(), Some(H2Level::GreatJourney.into()));
+    assert_eq!(Level::from(H3Level::LAST).next_mission(), None);
+    assert_eq!(Level::from(HrLevel::LAST).next_mission(), None);
+}
+
+#[test]
+fn publishes_mission_variables_on_change() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.level("03b");
+    s.tick();
+    assert_eq!(s.timer.variable("Mission Number"), Some("4/14"));
+    assert_eq!(s.timer.variable("Next Mission"), Some("The Arbiter"));
+
+    let events = s.timer.events.len();
+    s.ticks(5);
+    assert_eq!(s.timer.events.len(), events);
+
+    s.level("00a");
+    s.tick();
+    assert_eq!(s.timer.variable("Mission Number"), Some("-"));
+    assert_eq!(s.timer.variable("Next Mission"), Some("The Armory"));
+}
+
+#[test]
+fn publishes_current_game_level_and_bsp() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    s.level("040");
+    s.set(|p| &p.h3_bspstate, 0b1010u64);
+    s.tick();
+    assert_eq!(s.timer.variable("Current Game"), Some("Halo3"));
+    assert_eq!(s.timer.variable("Current Level"), Some("The Storm"));
+    assert_eq!(s.timer.variable("Current BSP (Raw)"), Some("10"));
+    assert_eq!(s.timer.variable("Current BSP"), Some("1, 3"));
+
+    let events = s.timer.events.len();
+    s.ticks(5);
+    assert_eq!(s.timer.events.len(), events);
+
+    s.set(|p| &p.h3_bspstate, 0b1011u64);
+    s.tick();
+    assert_eq!(s.timer.variable("Current BSP"), Some("0, 1, 3"));
+    assert_eq!(s.timer.events.len(), events + 2);
+}
+
+#[test]
+fn h2_bsp_is_an_index() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.level("01b");
+    s.set(|p| &p.h2_bspstate, 3u8);
+    s.tick();
+    assert_eq!(s.timer.variable("Current Level"), Some("Cairo Station"));
+    assert_eq!(s.timer.variable("Current BSP (Raw)"), Some("3"));
+    assert_eq!(s.timer.variable("Current BSP"), Some("3"));
+}
+
+#[test]
+fn bsp_toggles_cover_levels_with_bsp_splits() {
+    let levels: Vec<Level> = BspLevels::levels().collect();
+    assert!(levels.contains(&H1Level::Keyes.into()));
+    assert!(levels.contains(&H4Level::Midnight.into()));
+    assert!(!levels.contains(&H2Level::Armory.into()));
+    assert!(!levels.contains(&HrLevel::NewAlexandria.into()));
+    assert!(!levels.contains(&H3Level::Epilogue.into()));
+    assert_eq!(BspLevels::key(H1Level::Keyes.into()), "bsp_h1_d20");
+}
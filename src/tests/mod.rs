@@ -0,0 +1,172 @@
+// Scenario tests for the start/split/reset rules. Each scenario builds the real pointer
+// tables against fake module bases, writes values into fake memory and runs the same
+// per-tick pipeline as the main loop against a recording timer.
+//
+// These only build for the host: cargo test --target x86_64-unknown-linux-gnu
+
+mod h1;
+mod h2;
+mod h3;
+mod h4;
+mod hr;
+mod igt;
+mod level;
+mod multigame;
+mod odst;
+mod offsets;
+mod replay;
+mod scan;
+
+use crate::memory::{DeepPtr, FakeMemory};
+use crate::scan::ScannedOffsets;
+use crate::timer::RecordingTimer;
+use crate::*;
+use asr::string::ArrayCString;
+use bytemuck::NoUninit;
+
+pub const MCC_VERSION: u16 = 3528;
+
+pub fn cstr<const N: usize>(s: &str) -> ArrayCString<N> {
+    let mut bytes = [0u8; N];
+    bytes[..s.len()].copy_from_slice(s.as_bytes());
+    bytemuck::cast(bytes)
+}
+
+// Every module loaded at a distinct fake base.
+pub fn fake_dlls() -> GameDLLs {
+    GameDLLs {
+        exe_mcc: asr::Address::new(0x1_4000_0000),
+        dll_halo1: asr::Address::new(0x1000_0000),
+        dll_halo2: asr::Address::new(0x2000_0000),
+        dll_halo3: asr::Address::new(0x3000_0000),
+        dll_halo4: asr::Address::new(0x4000_0000),
+        dll_halo3_odst: asr::Address::new(0x5000_0000),
+        dll_halo_reach: asr::Address::new(0x6000_0000),
+    }
+}
+
+pub struct Scenario {
+    pub game: MCCGame,
+    pub mem: FakeMemory,
+    pub ptrs: GamePointers,
+    pub state: GameState,
+    pub settings: Settings,
+    pub splitter: SplitterState,
+    pub timer: RecordingTimer,
+}
+
+impl Scenario {
+    pub fn new(game: MCCGame) -> Self {
+        let dlls = fake_dlls();
+        let version = FileVersion {
+            minor_version: MCC_VERSION,
+            ..Default::default()
+        };
+
+        let mut ptrs = GamePointers::default();
+        update_game_pointers(false, version, &dlls, &ScannedOffsets::default(), &mut ptrs);
+
+        let mut scenario = Self {
+            game,
+            mem: FakeMemory::zeroed(),
+            ptrs,
+            state: GameState::default(),
+            settings: Settings::default(),
+            splitter: SplitterState::default(),
+            timer: RecordingTimer::default(),
+        };
+        scenario.switch_game(game);
+        scenario.set(|p| &p.mcc_menuindicator, 1u8);
+        scenario
+    }
+
+    pub fn il(mut self) -> Self {
+        self.settings.level_mode = LevelMode::IndividualLevel;
+        self
+    }
+
+    pub fn switch_game(&mut self, game: MCCGame) {
+        self.game = game;
+        self.set(|p| &p.mcc_gameindicator, game as u8);
+    }
+
+    pub fn set<T: NoUninit>(&mut self, ptr: impl Fn(&GamePointers) -> &DeepPtr, value: T) {
+        let ptr = *ptr(&self.ptrs);
+        self.mem.set(&ptr, value);
+    }
+
+    pub fn level(&mut self, name: &str) {
+        match self.game {
+            MCCGame::Halo1 => self.set(|p| &p.h1_levelname, cstr::<32>(name)),
+            MCCGame::Halo2 => self.set(|p| &p.h2_levelname, cstr::<3>(name)),
+            MCCGame::Halo3 => self.set(|p| &p.h3_levelname, cstr::<3>(name)),
+            MCCGame::Halo4 => self.set(|p| &p.h4_levelname, cstr::<3>(name)),
+            MCCGame::ODST => self.set(|p| &p.odst_levelname, cstr::<4>(name)),
+            MCCGame::Reach => self.set(|p| &p.hr_levelname, cstr::<3>(name)),
+            MCCGame::Unknown => {}
+        }
+    }
+
+    pub fn load(&mut self, value: u8) {
+        self.set(|p| &p.mcc_loadindicator, value);
+    }
+
+    pub fn menu(&mut self, value: u8) {
+        self.set(|p| &p.mcc_menuindicator, value);
+    }
+
+    pub fn pgcr(&mut self, value: u8) {
+        self.set(|p| &p.mcc_pgcrindicator, value);
+    }
+
+    pub fn igt_float(&mut self, value: f32) {
+        self.set(|p| &p.mcc_igt_float, value);
+    }
+
+    pub fn tick(&mut self) {
+        update_game_state_all(&mut self.state, &self.mem, &self.ptrs);
+        update_timer(&mut self.state, &self.settings, &mut self.splitter, &mut self.timer);
+        self.timer.next_tick();
+    }
+
+    pub fn ticks(&mut self, count: usize) {
+        for _ in 0..count {
+            self.tick();
+        }
+    }
+
+    pub fn running(&self) -> bool {
+        matches!(self.timer.state, TimerState::Running | TimerState::Paused)
+    }
+
+    pub fn starts(&self) -> usize {
+        self.timer.starts().len()
+    }
+
+    pub fn splits(&self) -> usize {
+        self.timer.splits().len()
+    }
+
+    pub fn resets(&self) -> usize {
+        self.timer.resets().len()
+    }
+
+    // The start window used by H3 ILs, H4, ODST and Reach: IGT passing 0.167s.
+    pub fn igt_start(&mut self) {
+        self.igt_float(0.0);
+        self.tick();
+        self.igt_float(0.2);
+        self.tick();
+    }
+
+    // Runs IGT forward in 1/60s steps.
+    pub fn igt_advance(&mut self, from: f32, ticks: u32) -> f32 {
+        let mut igt = from;
+        for _ in 0..ticks {
+            igt += 1.0 / 60.0;
+            self.igt_float(igt);
+            self.tick();
+        }
+        igt
+    }
+}
@@ -0,0 +1,326 @@
+use super::*;
+
+// Plays the final moments of each game in a full game run.
+fn finish_game(s: &mut Scenario) {
+    match s.game {
+        MCCGame::Halo1 => {
+            s.level("d40");
+            s.set(|p| &p.h1_xpos, 1500.0f32);
+            s.set(|p| &p.h1_cinematic, false);
+            s.set(|p| &p.h1_cutsceneskip, false);
+            s.tick();
+            s.set(|p| &p.h1_cinematic, true);
+            s.tick();
+        }
+        MCCGame::Halo2 => {
+            s.level("08b");
+            s.tick();
+            s.set(|p| &p.h2_tickcounter, 1000u32);
+            s.set(|p| &p.h2_bspstate, 3u8);
+            s.tick();
+            s.set(|p| &p.h2_tickcounter, 1400u32);
+            s.set(|p| &p.h2_fadebyte, 1u8);
+            s.set(|p| &p.h2_letterbox, 0.5f32);
+            s.tick();
+            s.set(|p| &p.h2_letterbox, 0.97f32);
+            s.tick();
+        }
+        MCCGame::Halo3 => {
+            s.level("130");
+            s.tick();
+            s.load(1);
+            s.tick();
+        }
+        MCCGame::Halo4 | MCCGame::ODST | MCCGame::Reach => {
+            s.level(match s.game {
+                MCCGame::Halo4 => "m90",
+                MCCGame::ODST => "l300",
+                _ => "m70",
+            });
+            s.igt_advance(0.2, 120);
+            s.pgcr(1);
+            s.tick();
+        }
+        MCCGame::Unknown => unreachable!(),
+    }
+}
+
+// Plays the opening of each game's first level, which is what resumes a paused run.
+fn begin_game(s: &mut Scenario, game: MCCGame) {
+    s.switch_game(game);
+    s.load(0);
+    s.pgcr(0);
+    match game {
+        MCCGame::Halo1 => {
+            s.level("a10");
+            s.set(|p| &p.h1_xpos, -60.0f32);
+            s.set(|p| &p.h1_tickcounter, 300u32);
+            s.set(|p| &p.h1_cinematic, true);
+            s.tick();
+            s.set(|p| &p.h1_cinematic, false);
+            s.tick();
+        }
+        MCCGame::Halo2 => {
+            s.level("01a");
+            s.set(|p| &p.h2_tickcounter, 27u32);
+            s.tick();
+        }
+        MCCGame::Halo3 => {
+            s.level("010");
+            s.set(|p| &p.h3_theatertime, 20u32);
+            s.tick();
+        }
+        MCCGame::Halo4 | MCCGame::Reach => {
+            s.level("m10");
+            s.igt_start();
+        }
+        MCCGame::ODST => {
+            s.level("h100");
+            s.set(|p| &p.odst_streets, 0u8);
+            s.igt_start();
+        }
+        MCCGame::Unknown => unreachable!(),
+    }
+}
+
+fn start_fg(s: &mut Scenario) {
+    match s.game {
+        MCCGame::Halo1 => super::h1::start_fg(s),
+        MCCGame::Halo2 => super::h2::start_fg(s),
+        MCCGame::Halo3 => super::h3::start_fg(s),
+        MCCGame::Halo4 => super::h4::start_fg(s),
+        MCCGame::ODST => super::odst::start_fg(s),
+        MCCGame::Reach => super::hr::start_fg(s),
+        MCCGame::Unknown => unreachable!(),
+    }
+}
+
+const GAMES: [MCCGame; 6] = [MCCGame::Halo1, MCCGame::Halo2, MCCGame::Halo3, MCCGame::Halo4, MCCGame::ODST, MCCGame::Reach];
+
+#[test]
+fn pauses_at_the_end_of_every_game() {
+    for game in GAMES {
+        let mut s = Scenario::new(game);
+        start_fg(&mut s);
+        finish_game(&mut s);
+        assert!(s.splitter.multigame_pause, "{game:?}");
+        assert!(s.timer.game_time_paused, "{game:?}");
+    }
+}
+
+#[test]
+fn rta_games_split_on_their_ending() {
+    for game in [MCCGame::Halo1, MCCGame::Halo2] {
+        let mut s = Scenario::new(game);
+        start_fg(&mut s);
+        finish_game(&mut s);
+        let splits = s.splits();
+        s.tick();
+        assert_eq!(s.splits(), splits + 1, "{game:?}");
+    }
+}
+
+#[test]
+fn stays_paused_between_games() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    start_fg(&mut s);
+    finish_game(&mut s);
+    s.ticks(2);
+
+    // Credits, menus and loading into the next game don't resume.
+    s.menu(0);
+    s.ticks(10);
+    s.switch_game(MCCGame::Halo2);
+    s.load(1);
+    s.ticks(10);
+    s.menu(1);
+    s.load(0);
+    s.ticks(10);
+    assert!(s.splitter.multigame_pause);
+    assert!(s.timer.game_time_paused);
+}
+
+#[test]
+fn resumes_on_every_games_first_level() {
+    for (i, &game) in GAMES.iter().enumerate() {
+        let next = GAMES[(i + 1) % GAMES.len()];
+        let mut s = Scenario::new(game);
+        start_fg(&mut s);
+        finish_game(&mut s);
+        s.ticks(2);
+
+        begin_game(&mut s, next);
+        assert!(!s.splitter.multigame_pause, "{game:?} -> {next:?}");
+        assert!(s.running());
+    }
+}
+
+#[test]
+fn does_not_resume_on_later_levels() {
+    let mut s = Scenario::new(MCCGame::Halo3);
+    start_fg(&mut s);
+    finish_game(&mut s);
+    s.ticks(2);
+
+    s.switch_game(MCCGame::Halo4);
+    s.load(0);
+    s.pgcr(0);
+    s.level("m02");
+    s.igt_start();
+    assert!(s.splitter.multigame_pause);
+}
+
+#[test]
+fn no_pause_in_il_or_any_level() {
+    let mut s = Scenario::new(MCCGame::Halo4).il();
+    s.level("m90");
+    s.igt_start();
+    s.igt_advance(0.2, 120);
+    s.pgcr(1);
+    s.tick();
+    assert!(!s.splitter.multigame_pause);
+
+    let mut s = Scenario::new(MCCGame::Halo4);
+    s.settings.any_level = true;
+    s.level("m90");
+    s.igt_start();
+    s.igt_advance(0.2, 120);
+    s.pgcr(1);
+    s.tick();
+    assert!(!s.splitter.multigame_pause);
+}
+
+#[test]
+fn game_time_carries_over_between_games() {
+    let mut s = Scenario::new(MCCGame::Halo4);
+    start_fg(&mut s);
+    finish_game(&mut s);
+    s.ticks(2);
+    let finished = s.timer.game_time;
+    assert!(finished > Duration::ZERO);
+
+    begin_game(&mut s, MCCGame::Reach);
+    let igt = s.igt_advance(0.2, 60);
+    assert!(!s.splitter.multigame_pause);
+    assert_eq!(s.timer.game_time, finished + Duration::milliseconds((igt * 1000.0).round() as i64));
+}
+
+#[test]
+fn only_reads_the_active_game() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.tick();
+    assert!(s.state.h2_levelname.pair.is_some());
+    assert!(s.state.h1_levelname.pair.is_none());
+    assert!(s.state.h3_levelname.pair.is_none());
+    assert!(s.state.h4_levelname.pair.is_none());
+    assert!(s.state.odst_levelname.pair.is_none());
+    assert!(s.state.hr_levelname.pair.is_none());
+}
+
+#[test]
+fn forgets_the_previous_game_on_a_switch() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    s.set(|p| &p.h1_cinematic, true);
+    s.tick();
+    assert_eq!(s.state.h1_cinematic.pair.map(|p| p.current), Some(true));
+
+    s.switch_game(MCCGame::Halo2);
+    s.set(|p| &p.h1_cinematic, false);
+    s.tick();
+    assert!(s.state.h1_cinematic.pair.is_none());
+
+    // Coming back starts from what the game reads now, not what it read before the switch.
+    s.switch_game(MCCGame::Halo1);
+    s.tick();
+    assert_eq!(s.state.h1_cinematic.pair.map(|p| p.old), Some(false));
+    assert!(s.state.h2_levelname.pair.is_none());
+}
+
+fn trilogy(game: MCCGame) -> Scenario {
+    let mut s = Scenario::new(game);
+    s.settings.set_route(&[MCCGame::Halo1, MCCGame::Halo2, MCCGame::Halo3]);
+    s
+}
+
+#[test]
+fn route_resumes_only_on_the_next_game() {
+    let mut s = trilogy(MCCGame::Halo1);
+    start_fg(&mut s);
+    s.tick();
+    assert_eq!(s.timer.variable("Multigame Route"), Some("Halo: CE (1/3)"));
+    finish_game(&mut s);
+    s.ticks(2);
+    assert_eq!(s.timer.variable("Multigame Route"), Some("Waiting for Halo 2"));
+
+    begin_game(&mut s, MCCGame::Halo3);
+    s.tick();
+    assert!(s.splitter.multigame_pause);
+    assert_eq!(s.timer.variable("Multigame Route"), Some("Halo 3 started out of order, expected Halo 2"));
+
+    begin_game(&mut s, MCCGame::Halo2);
+    s.tick();
+    assert!(!s.splitter.multigame_pause);
+    assert_eq!(s.timer.variable("Multigame Route"), Some("Halo 2 (2/3)"));
+}
+
+#[test]
+fn route_only_starts_on_its_first_game() {
+    let mut s = trilogy(MCCGame::Halo2);
+    begin_game(&mut s, MCCGame::Halo2);
+    s.tick();
+    assert_eq!(s.starts(), 0);
+    assert_eq!(s.timer.variable("Multigame Route"), Some("Halo 2 started out of order, expected Halo: CE"));
+
+    begin_game(&mut s, MCCGame::Halo1);
+    assert!(s.running());
+}
+
+#[test]
+fn route_stays_paused_after_the_last_game() {
+    let mut s = trilogy(MCCGame::Halo1);
+    start_fg(&mut s);
+    for game in [MCCGame::Halo2, MCCGame::Halo3] {
+        finish_game(&mut s);
+        s.ticks(2);
+        begin_game(&mut s, game);
+        assert!(!s.splitter.multigame_pause, "{game:?}");
+    }
+    finish_game(&mut s);
+    s.ticks(2);
+    assert_eq!(s.timer.variable("Multigame Route"), Some("Finished"));
+
+    begin_game(&mut s, MCCGame::Halo1);
+    s.tick();
+    assert!(s.splitter.multigame_pause);
+    assert_eq!(s.timer.variable("Multigame Route"), Some("Finished"));
+}
+
+#[test]
+fn route_follows_the_order_of_its_slots() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    s.settings.route_game_2 = RouteGame::Halo1;
+    s.settings.route_game_5 = RouteGame::Halo3;
+    assert_eq!(s.settings.route(), Some(vec![MCCGame::Halo1, MCCGame::Halo3]));
+
+    start_fg(&mut s);
+    finish_game(&mut s);
+    s.ticks(2);
+    assert_eq!(s.timer.variable("Multigame Route"), Some("Waiting for Halo 3"));
+
+    begin_game(&mut s, MCCGame::Halo2);
+    s.tick();
+    assert!(s.splitter.multigame_pause);
+
+    begin_game(&mut s, MCCGame::Halo3);
+    s.tick();
+    assert!(!s.splitter.multigame_pause);
+    assert_eq!(s.timer.variable("Multigame Route"), Some("Halo 3 (2/2)"));
+}
+
+#[test]
+fn no_route_variable_in_any_order() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    start_fg(&mut s);
+    s.tick();
+    assert_eq!(s.timer.variable("Multigame Route"), None);
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+const LEVELS: &[&str] = &["c100", "h100", "sc10", "sc11", "sc13", "sc12", "sc14", "sc15", "l200", "l300"];
+
+pub fn start_fg(s: &mut Scenario) {
+    s.level("h100");
+    s.set(|p| &p.odst_streets, 0u8);
+    s.igt_start();
+    assert!(s.running(), "full game run should start on Mombasa Streets");
+}
+
+#[test]
+fn fg_starts_on_mombasa_streets() {
+    let mut s = Scenario::new(MCCGame::ODST);
+    start_fg(&mut s);
+    assert_eq!(s.timer.starts(), vec![1]);
+}
+
+#[test]
+fn fg_does_not_start_on_later_streets_segments() {
+    let mut s = Scenario::new(MCCGame::ODST);
+    s.level("h100");
+    s.set(|p| &p.odst_streets, 2u8);
+    s.igt_start();
+    assert_eq!(s.starts(), 0);
+}
+
+#[test]
+fn fg_does_not_start_on_later_levels() {
+    for &level in &LEVELS[2..] {
+        let mut s = Scenario::new(MCCGame::ODST);
+        s.level(level);
+        s.igt_start();
+        assert_eq!(s.starts(), 0, "{level}");
+    }
+}
+
+#[test]
+fn il_starts_on_every_level() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::ODST).il();
+        s.level(level);
+        s.igt_start();
+        assert_eq!(s.timer.starts(), vec![1], "{level}");
+    }
+}
+
+#[test]
+fn il_records_streets_segment() {
+    let mut s = Scenario::new(MCCGame::ODST).il();
+    s.level("h100");
+    s.set(|p| &p.odst_streets, 3u8);
+    s.igt_start();
+    assert_eq!(s.splitter.started_scene, 3);
+}
+
+#[test]
+fn fg_resets_on_prepare_to_drop() {
+    let mut s = Scenario::new(MCCGame::ODST);
+    start_fg(&mut s);
+    s.level("c100");
+    s.igt_advance(0.2, 120);
+    s.igt_float(0.1);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_resets_on_mombasa_streets_revert() {
+    let mut s = Scenario::new(MCCGame::ODST);
+    start_fg(&mut s);
+    s.igt_advance(0.2, 120);
+    s.igt_float(0.1);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn fg_does_not_reset_on_later_level_revert() {
+    let mut s = Scenario::new(MCCGame::ODST);
+    start_fg(&mut s);
+    s.level("sc10");
+    s.igt_advance(0.2, 120);
+    s.igt_float(0.1);
+    s.tick();
+    assert_eq!(s.resets(), 0);
+}
+
+#[test]
+fn il_resets_only_on_started_streets_segment() {
+    let mut s = Scenario::new(MCCGame::ODST).il();
+    s.level("h100");
+    s.set(|p| &p.odst_streets, 3u8);
+    s.igt_start();
+    s.set(|p| &p.odst_streets, 4u8);
+    s.igt_advance(0.2, 120);
+    s.igt_float(0.1);
+    s.tick();
+    assert_eq!(s.resets(), 0);
+
+    s.set(|p| &p.odst_streets, 3u8);
+    s.igt_advance(0.1, 120);
+    s.igt_float(0.1);
+    s.tick();
+    assert_eq!(s.resets(), 1);
+}
+
+#[test]
+fn splits_after_pgcr() {
+    for &level in LEVELS {
+        let mut s = Scenario::new(MCCGame::ODST).il();
+        s.level(level);
+        s.igt_start();
+        s.igt_advance(0.2, 120);
+        s.pgcr(1);
+        s.ticks(2);
+        assert_eq!(s.splits(), 1, "{level}");
+    }
+}
+
+#[test]
+fn bsp_splits_once_the_level_is_in() {
+    let mut s = Scenario::new(MCCGame::ODST);
+    s.settings.bsp_mode = true;
+    start_fg(&mut s);
+
+    // Still within the level's first half second
+    s.set(|p| &p.odst_bspstate, 0b011u32);
+    s.tick();
+    assert_eq!(s.splits(), 0);
+
+    s.igt_float(10.0);
+    s.set(|p| &p.odst_bspstate, 0b111u32);
+    s.tick();
+    assert_eq!(s.splits(), 1);
+    // Mombasa Streets also splits on the first unload of 1
+    s.set(|p| &p.odst_bspstate, 0b101u32);
+    s.tick();
+    assert_eq!(s.splits(), 2);
+}
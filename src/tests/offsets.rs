@@ -0,0 +1,248 @@
+use super::*;
+use crate::offsets::{closest, for_version, OFFSETS};
+use crate::timer::TimerCommand;
+
+fn pointers(minor_version: u16, is_winstore: bool, dlls: &GameDLLs) -> GamePointers {
+    let version = FileVersion {
+        minor_version,
+        ..Default::default()
+    };
+    let mut ptrs = GamePointers::default();
+    update_game_pointers(is_winstore, version, dlls, &ScannedOffsets::default(), &mut ptrs);
+    ptrs
+}
+
+#[test]
+fn versions_are_sorted_and_unique() {
+    for pair in OFFSETS.windows(2) {
+        assert!(pair[0].version < pair[1].version, "{} listed before {}", pair[0].version, pair[1].version);
+    }
+}
+
+#[test]
+fn every_version_has_a_complete_pointer_set() {
+    for offsets in OFFSETS {
+        for is_winstore in [false, true] {
+            if offsets.mcc(is_winstore).is_none() {
+                continue;
+            }
+            let ptrs = pointers(offsets.version, is_winstore, &fake_dlls());
+            for (name, ptr) in ptrs.named() {
+                let store = if is_winstore { "WinStore" } else { "Steam" };
+                assert!(!ptr.is_null(), "{} {} has no {}", offsets.version, store, name);
+                assert!(
+                    ptr.path().first().is_some_and(|&offset| offset != 0),
+                    "{} {} has no offset for {}",
+                    offsets.version,
+                    store,
+                    name
+                );
+            }
+            assert!(ptrs.fadescale > 0.0, "{} has no fade scale", offsets.version);
+            for &level in H1Level::ALL {
+                assert_ne!(ptrs.h1_checklist.get(level), 0, "{} has no checksum for {}", offsets.version, level.code());
+            }
+        }
+    }
+}
+
+// Games whose globals share an address in a build, which are known to be unverified.
+const UNVERIFIED_DUPLICATES: &[(u16, &str)] = &[(3073, "h2")];
+
+#[test]
+fn globals_of_a_game_have_their_own_addresses() {
+    for offsets in OFFSETS {
+        let (h1, h2, h3, hr, odst, h4) = (&offsets.h1, &offsets.h2, &offsets.h3, &offsets.hr, &offsets.odst, &offsets.h4);
+        let games: [(&str, Vec<u64>); 6] = [
+            ("h1", vec![h1.tickcounter, h1.igt, h1.bspstate, h1.globals, h1.map, h1.cinflags, h1.coords, h1.fade]),
+            ("h2", vec![h2.levelname, h2.igt, h2.bspstate, h2.deathflag, h2.tickcounter, h2.graphics, h2.cinflags, h2.coords, h2.fade]),
+            ("h3", vec![h3.levelname, h3.theatertime, h3.tickcounter, h3.bspstate, h3.deathflag]),
+            ("hr", vec![hr.levelname, hr.bspstate, hr.deathflag]),
+            ("odst", vec![odst.levelname, odst.streets, odst.bspstate, odst.deathflag]),
+            ("h4", vec![h4.levelname, h4.bspstate]),
+        ];
+        for (game, globals) in games {
+            let mut unique = globals.clone();
+            unique.sort_unstable();
+            unique.dedup();
+            let known = UNVERIFIED_DUPLICATES.contains(&(offsets.version, game));
+            assert_eq!(unique.len() < globals.len(), known, "{} {} globals sharing an address", offsets.version, game);
+        }
+    }
+}
+
+#[test]
+fn winstore_is_supported_from_3272() {
+    for offsets in OFFSETS {
+        assert_eq!(offsets.mcc(true).is_some(), offsets.version >= 3272, "{}", offsets.version);
+    }
+}
+
+#[test]
+fn newer_versions_only_guess_the_latest_offsets() {
+    let latest = OFFSETS.last().unwrap();
+    assert!(for_version(latest.version + 1).is_none());
+    assert_eq!(closest(latest.version + 1).unwrap().version, latest.version);
+
+    // Nothing was found by signature, so none of the guesses are used
+    let ptrs = pointers(latest.version + 1, false, &fake_dlls());
+    assert!(ptrs.named().iter().all(|(_, ptr)| ptr.is_null()));
+}
+
+#[test]
+fn newer_versions_are_reported_as_unsupported() {
+    let newer = OFFSETS.last().unwrap().version + 1;
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.state.mcc_version = format!("1.{}.0.0", newer);
+    s.ptrs = pointers(newer, false, &fake_dlls());
+    s.level("01a");
+    s.tick();
+    assert_eq!(s.timer.variable("Splitter Status"), Some(format!("Unsupported MCC version 1.{}.0.0", newer).as_str()));
+    assert_eq!(s.starts(), 0);
+}
+
+// 3495 and 3498 had no MCC offsets before the table either, so nothing could be read on them.
+#[test]
+fn builds_without_mcc_offsets_are_unsupported() {
+    for version in [3495, 3498] {
+        assert!(for_version(version).is_none(), "{version}");
+        let ptrs = pointers(version, false, &fake_dlls());
+        assert!(ptrs.named().iter().all(|(_, ptr)| ptr.is_null()), "{version}");
+
+        let mut s = Scenario::new(MCCGame::Halo1);
+        s.state.mcc_version = format!("1.{}.0.0", version);
+        s.ptrs = ptrs;
+        s.tick();
+        assert_eq!(s.timer.variable("Splitter Status"), Some(format!("Unsupported MCC version 1.{}.0.0", version).as_str()));
+    }
+}
+
+#[test]
+fn unknown_older_versions_have_no_pointers() {
+    assert!(for_version(2000).is_none());
+    assert!(for_version(3000).is_none());
+
+    let ptrs = pointers(2000, false, &fake_dlls());
+    assert!(ptrs.named().iter().all(|(_, ptr)| ptr.is_null()));
+}
+
+#[test]
+fn unloaded_dlls_leave_their_pointers_unset() {
+    let dlls = GameDLLs {
+        dll_halo4: asr::Address::NULL,
+        ..fake_dlls()
+    };
+    let ptrs = pointers(OFFSETS.last().unwrap().version, false, &dlls);
+    for (name, ptr) in ptrs.named() {
+        assert_eq!(ptr.is_null(), name.starts_with("h4_"), "{}", name);
+    }
+}
+
+#[test]
+fn dll_vars_are_only_published_on_change() {
+    let mut timer = RecordingTimer::default();
+    let dlls = fake_dlls();
+    dlls.set_timer_vars(None, &mut timer);
+    assert_eq!(timer.events.len(), 6);
+
+    dlls.set_timer_vars(Some(&dlls), &mut timer);
+    assert_eq!(timer.events.len(), 6);
+
+    let unloaded = GameDLLs {
+        dll_halo4: asr::Address::NULL,
+        ..dlls
+    };
+    unloaded.set_timer_vars(Some(&dlls), &mut timer);
+    assert_eq!(timer.events.len(), 7);
+    assert_eq!(timer.variable("dll_h4"), Some(asr::Address::NULL.to_string().as_str()));
+}
+
+#[test]
+fn only_a_running_game_needs_its_dll_loaded() {
+    let unloaded = GameDLLs {
+        dll_halo4: asr::Address::NULL,
+        ..fake_dlls()
+    };
+    assert!(!unloaded.is_loaded(MCCGame::Halo4));
+    assert!(unloaded.is_loaded(MCCGame::Halo3));
+    assert!(unloaded.is_loaded(MCCGame::Unknown));
+}
+
+#[test]
+fn builds_without_winstore_offsets_leave_pointers_missing() {
+    let ptrs = pointers(OFFSETS.first().unwrap().version, true, &fake_dlls());
+    let mem = FakeMemory::zeroed();
+    for (name, ptr) in ptrs.named() {
+        assert_eq!(ptr.resolve::<u8>(&mem), Resolution::Missing, "{}", name);
+    }
+}
+
+#[test]
+fn pointers_of_unloaded_dlls_fail_to_resolve() {
+    let dlls = GameDLLs {
+        dll_halo2: asr::Address::NULL,
+        ..fake_dlls()
+    };
+    let ptrs = pointers(OFFSETS.last().unwrap().version, false, &dlls);
+    let mem = FakeMemory::zeroed();
+    assert_eq!(ptrs.h2_igt.resolve::<u32>(&mem), Resolution::Failed);
+    assert_eq!(ptrs.h1_igt.resolve::<u32>(&mem), Resolution::Resolved(0));
+    assert_eq!(ptrs.h1_igt.resolve::<u32>(&FakeMemory::default()), Resolution::Failed);
+}
+
+#[test]
+fn game_without_offsets_is_disabled() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.ptrs.h2_tickcounter = DeepPtr::default();
+    s.level("01a");
+    s.set(|p| &p.h2_igt, 0u32);
+    s.tick();
+    assert_eq!(s.timer.variable("Splitter Status"), Some("Halo2 not supported on this MCC version, missing h2_tickcounter"));
+    assert_eq!(s.timer.variable("Current Level"), None);
+    assert_eq!(s.starts(), 0);
+}
+
+#[test]
+fn failed_reads_are_reported() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.tick();
+    assert_eq!(s.timer.variable("Splitter Status"), Some("OK"));
+
+    s.ptrs.h2_graphics = DeepPtr::new_64bit(asr::Address::NULL, &[0x10]);
+    s.tick();
+    assert_eq!(s.timer.variable("Splitter Status"), Some("Couldn't read h2_graphics"));
+    super::h2::start_fg(&mut s);
+}
+
+#[test]
+fn diagnostics_cover_every_pointer() {
+    let mut s = Scenario::new(MCCGame::Halo1);
+    s.tick();
+    set_debug_timer_vars(&s.state, &mut s.timer);
+    let pointer_vars = s.timer.events.iter().filter(|e| matches!(&e.command, TimerCommand::SetVariable(k, _) if k.ends_with(" Pointer"))).count();
+    assert_eq!(pointer_vars, s.ptrs.named().len());
+    assert_eq!(s.timer.variable("H1 Tick Counter Pointer"), Some("Resolved"));
+    assert_eq!(s.timer.variable("H2 Tick Counter Pointer"), Some("Not read"));
+}
+
+#[test]
+fn diagnostics_count_failed_ticks_and_keep_the_last_value() {
+    let mut s = Scenario::new(MCCGame::Halo2);
+    s.set(|p| &p.h2_graphics, 5u8);
+    s.tick();
+    set_debug_timer_vars(&s.state, &mut s.timer);
+
+    s.ptrs.h2_graphics = DeepPtr::new_64bit(asr::Address::NULL, &[0x10]);
+    s.ticks(3);
+    set_debug_timer_vars(&s.state, &mut s.timer);
+    assert_eq!(s.timer.variable("H2 Graphics Pointer"), Some("Failed"));
+    assert_eq!(s.timer.variable("H2 Graphics Failed Ticks"), Some("3"));
+    assert_eq!(s.timer.variable("H2 Graphics"), Some("5"));
+
+    // A pointer that isn't set for this version has no value to keep
+    s.ptrs.h2_graphics = DeepPtr::default();
+    s.tick();
+    set_debug_timer_vars(&s.state, &mut s.timer);
+    assert_eq!(s.timer.variable("H2 Graphics Pointer"), Some("Missing"));
+    assert_eq!(s.timer.variable("H2 Graphics"), Some(""));
+}
@@ -0,0 +1,127 @@
+// Recorded-style traces replayed through replay_trace, for behaviour that spans menus and
+// several levels.
+use super::*;
+
+fn replay(trace: &str) -> RecordingTimer {
+    let mut timer = RecordingTimer::default();
+    replay_trace(trace, &mut timer).unwrap();
+    timer
+}
+
+fn with_settings(settings: &str, ticks: &str) -> String {
+    format!("mcctrace 1 1.3528.0.0 0\nsettings level_mode=FullGame {}\n{}", settings, ticks)
+}
+
+// Starts on Pillar of Autumn, reverts, loads Truth and Reconciliation from the menu, reverts
+// there, re-enters it from the menu, then loads Silent Cartographer with the level name
+// arriving a few ticks after the menu indicator.
+const H1_MENU_LOADS: &str = "\
+0 mcc_gameindicator=0 mcc_menuindicator=1 mcc_loadindicator=0 mcc_pgcrindicator=0 h1_levelname=a10 h1_bspstate=0 h1_xpos=-60 h1_tickcounter=300 h1_igt=0 h1_cinematic=1 h1_cutsceneskip=0 h1_gamewon=0 h1_deathflag=0
+1 h1_cinematic=0
+5 h1_igt=100 h1_tickcounter=400
+10 h1_igt=60 h1_tickcounter=360
+20 mcc_menuindicator=0
+30 mcc_menuindicator=1 h1_levelname=b30 h1_igt=0 h1_tickcounter=0
+40 h1_igt=200 h1_tickcounter=200
+50 h1_igt=150 h1_tickcounter=150
+55 mcc_menuindicator=0
+58 mcc_menuindicator=1
+60 h1_igt=120 h1_tickcounter=120
+65 mcc_menuindicator=0
+70 mcc_menuindicator=1
+75 h1_levelname=b40 h1_igt=0 h1_tickcounter=0
+80 h1_igt=90 h1_tickcounter=90
+85 h1_igt=30 h1_tickcounter=30
+";
+
+// Starts in The Armory, loads Outskirts from the menu, reverts, then loads Metropolis.
+const H2_MENU_LOADS: &str = "\
+0 mcc_gameindicator=1 mcc_menuindicator=1 mcc_loadindicator=0 mcc_pgcrindicator=0 h2_levelname=01a h2_bspstate=0 h2_tickcounter=20 h2_igt=20 h2_fadebyte=0 h2_letterbox=0
+1 h2_tickcounter=27 h2_igt=27
+10 h2_tickcounter=300 h2_igt=300
+20 mcc_menuindicator=0
+30 mcc_menuindicator=1 h2_levelname=03a h2_tickcounter=0 h2_igt=0
+40 h2_tickcounter=500 h2_igt=500
+50 h2_tickcounter=400 h2_igt=400
+60 mcc_menuindicator=0
+70 mcc_menuindicator=1 h2_levelname=03b h2_tickcounter=0 h2_igt=0
+80 h2_tickcounter=100 h2_igt=100
+";
+
+// Loads Silent Cartographer from the menu, goes back to the menu and re-enters it, then
+// finishes it with a normal load into Assault on the Control Room.
+const H1_MENU_REENTRY: &str = "\
+0 mcc_gameindicator=0 mcc_menuindicator=1 mcc_loadindicator=0 mcc_pgcrindicator=0 h1_levelname=a10 h1_bspstate=0 h1_xpos=-60 h1_tickcounter=300 h1_igt=0 h1_cinematic=1 h1_cutsceneskip=0 h1_gamewon=0 h1_deathflag=0
+1 h1_cinematic=0
+5 h1_igt=100 h1_tickcounter=400
+20 mcc_menuindicator=0
+30 mcc_menuindicator=1 h1_levelname=b30 h1_igt=0 h1_tickcounter=0
+40 h1_igt=200 h1_tickcounter=200
+50 mcc_menuindicator=0
+60 mcc_menuindicator=1 h1_igt=0 h1_tickcounter=0
+70 h1_igt=300 h1_tickcounter=300
+80 mcc_loadindicator=1
+82 h1_levelname=b40
+90 mcc_loadindicator=0 h1_igt=0 h1_tickcounter=0
+";
+
+#[test]
+fn h1_sq_split_fires_once_per_menu_load() {
+    let timer = replay(&with_settings("sq_split=1", H1_MENU_LOADS));
+    assert_eq!(timer.starts(), vec![1]);
+    assert_eq!(timer.splits(), vec![30, 75]);
+    assert!(timer.resets().is_empty());
+}
+
+#[test]
+fn h2_sq_split_fires_once_per_menu_load() {
+    let timer = replay(&with_settings("sq_split=1", H2_MENU_LOADS));
+    assert_eq!(timer.starts(), vec![1]);
+    assert_eq!(timer.splits(), vec![30, 70]);
+    assert!(timer.resets().is_empty());
+}
+
+#[test]
+fn sq_split_is_off_by_default() {
+    for trace in [H1_MENU_LOADS, H2_MENU_LOADS] {
+        let timer = replay(&with_settings("sq_split=0", trace));
+        assert_eq!(timer.starts(), vec![1]);
+        assert!(timer.splits().is_empty());
+    }
+}
+
+#[test]
+fn unticked_bsp_levels_are_kept_in_the_trace_header() {
+    let mut settings = Settings::default();
+    settings.bsp_levels.disabled = vec![H1Level::Keyes.into(), H4Level::Dawn.into()];
+    let line = settings.trace_line();
+    assert!(line.ends_with(" bsp_levels_off=bsp_h1_d20,bsp_h4_m10"), "{line}");
+
+    let mut replayed = Settings::default();
+    replayed.apply_trace_option("bsp_levels_off", "bsp_h1_d20,bsp_h4_m10");
+    assert_eq!(replayed.bsp_levels.disabled, settings.bsp_levels.disabled);
+}
+
+#[test]
+fn multigame_route_is_kept_in_the_trace_header() {
+    let mut settings = Settings::default();
+    settings.set_route(&[MCCGame::Reach, MCCGame::Halo1]);
+    let line = settings.trace_line();
+    assert!(line.contains(" multigame_route=Reach,Halo1 "), "{line}");
+
+    let mut replayed = Settings::default();
+    replayed.apply_trace_option("multigame_route", "Reach,Halo1");
+    assert_eq!(replayed.route(), Some(vec![MCCGame::Reach, MCCGame::Halo1]));
+}
+
+#[test]
+fn sq_split_ignores_re_entering_the_same_level() {
+    let without = replay(&with_settings("sq_split=0", H1_MENU_REENTRY));
+    let timer = replay(&with_settings("sq_split=1", H1_MENU_REENTRY));
+    assert_eq!(timer.starts(), vec![1]);
+
+    // Only the load from the menu into Silent Cartographer adds a split
+    let mut expected = vec![30];
+    expected.extend(without.splits());
+    assert_eq!(timer.splits(), expected);
+}
@@ -0,0 +1,206 @@
+use super::*;
+use crate::offsets::OFFSETS;
+use crate::scan::{scan_module, Global, GlobalScan, Module, ModuleMemory, RipRelative, ScannedOffsets};
+use asr::{signature::Signature, Address};
+
+const IMAGE_SIZE: usize = 0x30000;
+
+// Module images held in memory, each mapped at its own base.
+#[derive(Default)]
+struct ModuleImages {
+    modules: Vec<(&'static str, Address, Vec<u8>)>,
+}
+
+impl ModuleImages {
+    fn insert(&mut self, name: &'static str, base: Address, image: Vec<u8>) {
+        self.modules.retain(|(n, _, _)| *n != name);
+        self.modules.push((name, base, image));
+    }
+}
+
+impl ModuleMemory for ModuleImages {
+    fn module_range(&self, name: &str) -> Option<(Address, u64)> {
+        let (_, base, image) = self.modules.iter().find(|(n, _, _)| *n == name)?;
+        Some((*base, image.len() as u64))
+    }
+
+    fn read_bytes(&self, address: Address, buf: &mut [u8]) -> bool {
+        for (_, base, image) in &self.modules {
+            let Some(start) = address.value().checked_sub(base.value()) else { continue };
+            let Some(bytes) = image.get(start as usize..start as usize + buf.len()) else {
+                continue;
+            };
+            buf.copy_from_slice(bytes);
+            return true;
+        }
+        false
+    }
+}
+
+// mov rax, [rip+disp32]; mov ecx, eax
+const TICKCOUNTER: RipRelative<9> = RipRelative {
+    signature: Signature::new("48 8B 05 ?? ?? ?? ?? 89 C1"),
+    disp: 3,
+    next: 7,
+};
+
+// cmp byte ptr [rip+disp32], 1; je
+const MENUSTATE: RipRelative<8> = RipRelative {
+    signature: Signature::new("80 3D ?? ?? ?? ?? 01 74"),
+    disp: 2,
+    next: 7,
+};
+
+// Writes `mov rax, [rip+disp32]; mov ecx, eax` at `at`, referencing `target`.
+fn image_with_load(at: usize, target: usize) -> Vec<u8> {
+    let mut image = vec![0xCC; IMAGE_SIZE];
+    let disp = target as i64 - (at + 7) as i64;
+    image[at..at + 3].copy_from_slice(&[0x48, 0x8B, 0x05]);
+    image[at + 3..at + 7].copy_from_slice(&(disp as i32).to_le_bytes());
+    image[at + 7..at + 9].copy_from_slice(&[0x89, 0xC1]);
+    image
+}
+
+fn scan(image: Vec<u8>, signature: &dyn GlobalScan) -> Option<u64> {
+    let base = fake_dlls().dll_halo1;
+    let mut images = ModuleImages::default();
+    images.insert("halo1.dll", base, image);
+    signature.scan(&images, (base, IMAGE_SIZE as u64))
+}
+
+#[test]
+fn resolves_forward_reference() {
+    assert_eq!(scan(image_with_load(0x1234, 0x20000), &TICKCOUNTER), Some(0x20000));
+}
+
+#[test]
+fn resolves_backward_reference() {
+    assert_eq!(scan(image_with_load(0x28000, 0x100), &TICKCOUNTER), Some(0x100));
+}
+
+#[test]
+fn finds_match_across_chunk_boundary() {
+    // Scanning reads 0x10000 bytes at a time.
+    assert_eq!(scan(image_with_load(0xFFFC, 0x2000), &TICKCOUNTER), Some(0x2000));
+}
+
+#[test]
+fn finds_match_at_end_of_image() {
+    assert_eq!(scan(image_with_load(IMAGE_SIZE - 9, 0x2000), &TICKCOUNTER), Some(0x2000));
+}
+
+#[test]
+fn rejects_targets_outside_the_module() {
+    assert_eq!(scan(image_with_load(0x1000, IMAGE_SIZE + 0x100), &TICKCOUNTER), None);
+}
+
+#[test]
+fn missing_signature_is_not_found() {
+    assert_eq!(scan(vec![0xCC; IMAGE_SIZE], &TICKCOUNTER), None);
+}
+
+#[test]
+fn scans_only_globals_in_the_module() {
+    let base = fake_dlls().dll_halo1;
+    let mut images = ModuleImages::default();
+    let mut image = image_with_load(0x1000, 0x8000);
+    image[0x2000..0x2008].copy_from_slice(&[0x80, 0x3D, 0xF9, 0x1F, 0x00, 0x00, 0x01, 0x74]);
+    images.insert("halo1.dll", base, image);
+
+    let signatures: &[(Global, &dyn GlobalScan)] = &[(Global::H1TickCounter, &TICKCOUNTER), (Global::MccMenuState, &MENUSTATE)];
+    let found = scan_module(&images, Module::Halo1, (base, IMAGE_SIZE as u64), signatures);
+    assert_eq!(found.get(&Global::H1TickCounter), Some(&0x8000));
+    assert_eq!(found.get(&Global::MccMenuState), None);
+
+    let found = scan_module(&images, Module::Exe, (base, IMAGE_SIZE as u64), signatures);
+    assert_eq!(found.get(&Global::MccMenuState), Some(&0x4000));
+}
+
+#[test]
+fn rescans_only_when_a_module_moves() {
+    let mut images = ModuleImages::default();
+    let mut dlls = fake_dlls();
+    for module in Module::ALL {
+        images.insert(module.name("MCC-Win64-Shipping.exe"), module.base(&dlls), vec![0; 0x100]);
+    }
+
+    let mut scanned = ScannedOffsets::default();
+    assert!(scanned.update(&images, "MCC-Win64-Shipping.exe", &dlls));
+    assert!(!scanned.update(&images, "MCC-Win64-Shipping.exe", &dlls));
+
+    dlls.dll_halo2 = Address::new(0x2800_0000);
+    images.insert("halo2.dll", dlls.dll_halo2, vec![0; 0x100]);
+    assert!(scanned.update(&images, "MCC-Win64-Shipping.exe", &dlls));
+    assert!(!scanned.update(&images, "MCC-Win64-Shipping.exe", &dlls));
+}
+
+fn pointers(minor_version: u16, scanned: &ScannedOffsets) -> GamePointers {
+    let version = FileVersion {
+        minor_version,
+        ..Default::default()
+    };
+    let mut ptrs = GamePointers::default();
+    update_game_pointers(false, version, &fake_dlls(), scanned, &mut ptrs);
+    ptrs
+}
+
+#[test]
+fn unknown_builds_use_scanned_offsets() {
+    let latest = OFFSETS.last().unwrap();
+    let mut scanned = ScannedOffsets::default();
+    for global in Global::ALL {
+        scanned.insert(global, 0x9000);
+    }
+    scanned.insert(Global::H1TickCounter, 0x1234);
+    scanned.insert(Global::MccMenuState, 0x5000);
+
+    let ptrs = pointers(latest.version + 1, &scanned);
+    assert_eq!(ptrs.h1_tickcounter.path(), &[0x1234]);
+    assert_eq!(ptrs.h2_tickcounter.path(), &[0x9000]);
+    assert_eq!(ptrs.mcc_loadindicator.path(), &[0x5000]);
+    assert_eq!(ptrs.mcc_menuindicator.path(), &[0x5000 + latest.mcc.menu]);
+}
+
+#[test]
+fn unknown_builds_need_every_global_scanned() {
+    let latest = OFFSETS.last().unwrap();
+    let mut scanned = ScannedOffsets::default();
+    for global in &Global::ALL[1..] {
+        scanned.insert(*global, 0x9000);
+    }
+    assert!(!scanned.found_all());
+
+    let ptrs = pointers(latest.version + 1, &scanned);
+    assert!(ptrs.h1_tickcounter.path().is_empty());
+    assert!(ptrs.mcc_loadindicator.path().is_empty());
+}
+
+#[test]
+fn known_builds_ignore_scanned_offsets() {
+    let latest = OFFSETS.last().unwrap();
+    let mut scanned = ScannedOffsets::default();
+    scanned.insert(Global::H1TickCounter, 0x1234);
+
+    let ptrs = pointers(latest.version, &scanned);
+    assert_eq!(ptrs.h1_tickcounter.path(), &[latest.h1.tickcounter]);
+}
+
+#[test]
+fn scans_a_module_once_its_range_can_be_found() {
+    let mut images = ModuleImages::default();
+    let mut dlls = GameDLLs {
+        exe_mcc: fake_dlls().exe_mcc,
+        ..Default::default()
+    };
+    images.insert("MCC-Win64-Shipping.exe", dlls.exe_mcc, vec![0; 0x100]);
+
+    let mut scanned = ScannedOffsets::default();
+    assert!(scanned.update(&images, "MCC-Win64-Shipping.exe", &dlls));
+
+    // halo1.dll shows up in the DLL list before its range can be queried
+    dlls.dll_halo1 = fake_dlls().dll_halo1;
+    assert!(!scanned.update(&images, "MCC-Win64-Shipping.exe", &dlls));
+    images.insert("halo1.dll", dlls.dll_halo1, vec![0; 0x100]);
+    assert!(scanned.update(&images, "MCC-Win64-Shipping.exe", &dlls));
+    assert!(!scanned.update(&images, "MCC-Win64-Shipping.exe", &dlls));
+}
@@ -0,0 +1,257 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use asr::{time::Duration, time_util::Instant, timer::TimerState};
+
+// Everything the splitter asks of the timer. The splitting logic only talks to this trait
+// so a run can be driven against a recorder instead of the real runtime.
+pub trait TimerBackend {
+    fn state(&self) -> TimerState;
+    fn start(&mut self);
+    fn split(&mut self);
+    fn reset(&mut self);
+    fn pause_game_time(&mut self);
+    fn resume_game_time(&mut self);
+    fn set_game_time(&mut self, time: Duration);
+    fn set_variable(&mut self, key: &str, value: &str);
+    fn set_variable_int(&mut self, key: &str, value: u64);
+    fn set_variable_float(&mut self, key: &str, value: f32);
+    // How often the runtime runs the splitter, in ticks per second.
+    fn set_tick_rate(&mut self, ticks_per_second: f64);
+    // Real time since the backend was created, used to check the game's speed.
+    fn now(&self) -> Duration;
+}
+
+pub struct AsrTimer {
+    clock: Instant,
+}
+
+impl AsrTimer {
+    pub fn new() -> Self {
+        Self { clock: Instant::now() }
+    }
+}
+
+impl Default for AsrTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimerBackend for AsrTimer {
+    fn state(&self) -> TimerState {
+        asr::timer::state()
+    }
+
+    fn start(&mut self) {
+        asr::timer::start();
+    }
+
+    fn split(&mut self) {
+        asr::timer::split();
+    }
+
+    fn reset(&mut self) {
+        asr::timer::reset();
+    }
+
+    fn pause_game_time(&mut self) {
+        asr::timer::pause_game_time();
+    }
+
+    fn resume_game_time(&mut self) {
+        asr::timer::resume_game_time();
+    }
+
+    fn set_game_time(&mut self, time: Duration) {
+        asr::timer::set_game_time(time);
+    }
+
+    fn set_variable(&mut self, key: &str, value: &str) {
+        asr::timer::set_variable(key, value);
+    }
+
+    fn set_variable_int(&mut self, key: &str, value: u64) {
+        asr::timer::set_variable_int(key, value);
+    }
+
+    fn set_variable_float(&mut self, key: &str, value: f32) {
+        asr::timer::set_variable_float(key, value);
+    }
+
+    fn set_tick_rate(&mut self, ticks_per_second: f64) {
+        asr::set_tick_rate(ticks_per_second);
+    }
+
+    fn now(&self) -> Duration {
+        self.clock.elapsed()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimerCommand {
+    Start,
+    Split,
+    Reset,
+    PauseGameTime,
+    ResumeGameTime,
+    SetGameTime(Duration),
+    SetVariable(String, String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimerEvent {
+    pub tick: u64,
+    pub command: TimerCommand,
+}
+
+// Records every command with the tick it was issued on and mimics the state transitions
+// of LiveSplit's timer, so the main loop behaves the same as it would against the runtime.
+pub struct RecordingTimer {
+    pub events: Vec<TimerEvent>,
+    pub tick: u64,
+    pub state: TimerState,
+    pub game_time: Duration,
+    pub game_time_paused: bool,
+    pub split_index: usize,
+    // Number of segments in the run. The split on the last segment ends the run.
+    pub segments: Option<usize>,
+    // Real time each tick takes, 120 Hz like the runtime's default tick rate.
+    pub tick_length: Duration,
+    // Tick rate last asked for. Doesn't change tick_length, which tests set themselves.
+    pub tick_rate: f64,
+}
+
+impl Default for RecordingTimer {
+    fn default() -> Self {
+        Self {
+            events: Vec::new(),
+            tick: 0,
+            state: TimerState::NotRunning,
+            game_time: Duration::ZERO,
+            game_time_paused: false,
+            split_index: 0,
+            segments: None,
+            tick_length: Duration::nanoseconds(1_000_000_000 / 120),
+            tick_rate: 120.0,
+        }
+    }
+}
+
+impl RecordingTimer {
+    pub fn with_segments(segments: usize) -> Self {
+        Self {
+            segments: Some(segments),
+            ..Default::default()
+        }
+    }
+
+    pub fn next_tick(&mut self) {
+        self.tick += 1;
+    }
+
+    fn record(&mut self, command: TimerCommand) {
+        self.events.push(TimerEvent { tick: self.tick, command });
+    }
+
+    // Ticks on which the given command was issued.
+    pub fn ticks_of(&self, command: &TimerCommand) -> Vec<u64> {
+        self.events.iter().filter(|e| &e.command == command).map(|e| e.tick).collect()
+    }
+
+    pub fn starts(&self) -> Vec<u64> {
+        self.ticks_of(&TimerCommand::Start)
+    }
+
+    pub fn splits(&self) -> Vec<u64> {
+        self.ticks_of(&TimerCommand::Split)
+    }
+
+    pub fn resets(&self) -> Vec<u64> {
+        self.ticks_of(&TimerCommand::Reset)
+    }
+
+    // Game time that was last set on or before the given tick.
+    pub fn game_time_at(&self, tick: u64) -> Option<Duration> {
+        self.events.iter().take_while(|e| e.tick <= tick).fold(None, |acc, e| match e.command {
+            TimerCommand::SetGameTime(time) => Some(time),
+            _ => acc,
+        })
+    }
+
+    // Last value a timer variable was set to.
+    pub fn variable(&self, key: &str) -> Option<&str> {
+        self.events.iter().rev().find_map(|e| match &e.command {
+            TimerCommand::SetVariable(k, v) if k == key => Some(v.as_str()),
+            _ => None,
+        })
+    }
+}
+
+impl TimerBackend for RecordingTimer {
+    fn state(&self) -> TimerState {
+        self.state
+    }
+
+    fn start(&mut self) {
+        self.record(TimerCommand::Start);
+        if self.state == TimerState::NotRunning {
+            self.state = TimerState::Running;
+            self.split_index = 0;
+            self.game_time = Duration::ZERO;
+            self.game_time_paused = false;
+        }
+    }
+
+    fn split(&mut self) {
+        self.record(TimerCommand::Split);
+        if self.state == TimerState::Running || self.state == TimerState::Paused {
+            self.split_index += 1;
+            if self.segments.is_some_and(|segments| self.split_index >= segments) {
+                self.state = TimerState::Ended;
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.record(TimerCommand::Reset);
+        self.state = TimerState::NotRunning;
+        self.split_index = 0;
+    }
+
+    fn pause_game_time(&mut self) {
+        self.record(TimerCommand::PauseGameTime);
+        self.game_time_paused = true;
+    }
+
+    fn resume_game_time(&mut self) {
+        self.record(TimerCommand::ResumeGameTime);
+        self.game_time_paused = false;
+    }
+
+    fn set_game_time(&mut self, time: Duration) {
+        self.record(TimerCommand::SetGameTime(time));
+        self.game_time = time;
+    }
+
+    fn set_variable(&mut self, key: &str, value: &str) {
+        self.record(TimerCommand::SetVariable(key.to_string(), value.to_string()));
+    }
+
+    fn set_variable_int(&mut self, key: &str, value: u64) {
+        self.record(TimerCommand::SetVariable(key.to_string(), value.to_string()));
+    }
+
+    fn set_variable_float(&mut self, key: &str, value: f32) {
+        self.record(TimerCommand::SetVariable(key.to_string(), value.to_string()));
+    }
+
+    fn set_tick_rate(&mut self, ticks_per_second: f64) {
+        self.tick_rate = ticks_per_second;
+    }
+
+    fn now(&self) -> Duration {
+        self.tick_length * self.tick as u32
+    }
+}
@@ -0,0 +1,257 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use asr::{string::ArrayCString, watcher::Watcher};
+use core::fmt::Write;
+
+// Trace format, one line per entry:
+//
+//   mcctrace 1 <mcc version> <winstore 0/1>
+//   settings <name>=<value> ...
+//   <tick> <watcher>=<value> ...
+//
+// Tick lines only list watchers whose value changed since the previous line, and ticks
+// where nothing changed are omitted entirely. A value of "-" means the read failed.
+pub const TRACE_MAGIC: &str = "mcctrace";
+pub const TRACE_VERSION: u32 = 1;
+
+const MISSING: &str = "-";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceError {
+    MissingHeader,
+    UnsupportedVersion(String),
+    BadLine(usize),
+    BadValue(usize, String),
+}
+
+impl core::fmt::Display for TraceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TraceError::MissingHeader => write!(f, "trace does not start with a {} header", TRACE_MAGIC),
+            TraceError::UnsupportedVersion(v) => write!(f, "unsupported trace version {}", v),
+            TraceError::BadLine(line) => write!(f, "malformed trace line {}", line),
+            TraceError::BadValue(line, name) => write!(f, "bad value for {} on trace line {}", name, line),
+        }
+    }
+}
+
+pub trait TraceValue: Copy {
+    fn encode(&self, out: &mut String);
+    fn decode(s: &str) -> Option<Self>;
+}
+
+macro_rules! impl_trace_value_display {
+    ($($t:ty),*) => {
+        $(
+            impl TraceValue for $t {
+                fn encode(&self, out: &mut String) {
+                    let _ = write!(out, "{}", self);
+                }
+
+                fn decode(s: &str) -> Option<Self> {
+                    s.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+// Float Display output is the shortest string that parses back to the same value.
+impl_trace_value_display!(u8, u16, u32, u64, f32);
+
+impl TraceValue for bool {
+    fn encode(&self, out: &mut String) {
+        out.push(if *self { '1' } else { '0' });
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        match s {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl<const N: usize> TraceValue for ArrayCString<N> {
+    fn encode(&self, out: &mut String) {
+        for &b in self.as_bytes() {
+            if b.is_ascii_graphic() && b != b'%' && b != b'=' && b != b'-' {
+                out.push(b as char);
+            } else {
+                let _ = write!(out, "%{:02X}", b);
+            }
+        }
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        let mut bytes = [0u8; N];
+        let mut len = 0;
+        let mut chars = s.bytes();
+        while let Some(b) = chars.next() {
+            let b = if b == b'%' {
+                let hi = (chars.next()? as char).to_digit(16)?;
+                let lo = (chars.next()? as char).to_digit(16)?;
+                (hi * 16 + lo) as u8
+            } else {
+                b
+            };
+            *bytes.get_mut(len)? = b;
+            len += 1;
+        }
+        Some(bytemuck::cast(bytes))
+    }
+}
+
+// Visits every watcher in GameState by name. See GameState::visit_watchers.
+pub trait WatcherVisitor {
+    fn visit<T: TraceValue>(&mut self, name: &'static str, watcher: &mut Watcher<T>);
+}
+
+#[derive(Default)]
+pub struct TraceRecorder {
+    tick: u64,
+    last: BTreeMap<&'static str, String>,
+    line: String,
+    changed: bool,
+}
+
+impl TraceRecorder {
+    pub fn header(mcc_version: &str, is_winstore: bool) -> String {
+        alloc::format!("{} {} {} {}", TRACE_MAGIC, TRACE_VERSION, mcc_version, is_winstore as u8)
+    }
+
+    pub fn begin_tick(&mut self) {
+        self.line.clear();
+        let _ = write!(self.line, "{}", self.tick);
+        self.changed = false;
+    }
+
+    // Returns the line for the tick, or None if no watcher changed since the last one.
+    pub fn finish_tick(&mut self) -> Option<&str> {
+        self.tick += 1;
+        self.changed.then_some(self.line.as_str())
+    }
+}
+
+impl WatcherVisitor for TraceRecorder {
+    fn visit<T: TraceValue>(&mut self, name: &'static str, watcher: &mut Watcher<T>) {
+        let mut value = String::new();
+        match &watcher.pair {
+            Some(pair) => pair.current.encode(&mut value),
+            None => value.push_str(MISSING),
+        }
+        if self.last.get(name) != Some(&value) {
+            let _ = write!(self.line, " {}={}", name, value);
+            self.last.insert(name, value);
+            self.changed = true;
+        }
+    }
+}
+
+pub struct TraceReader<'a> {
+    pub mcc_version: &'a str,
+    pub is_winstore: bool,
+    pub settings: Vec<(&'a str, &'a str)>,
+    lines: core::iter::Peekable<core::iter::Enumerate<core::str::Lines<'a>>>,
+    tick: u64,
+}
+
+impl<'a> TraceReader<'a> {
+    pub fn new(trace: &'a str) -> Result<Self, TraceError> {
+        let mut lines = trace.lines().enumerate().peekable();
+
+        let (_, header) = lines.next().ok_or(TraceError::MissingHeader)?;
+        let mut parts = header.split_whitespace();
+        if parts.next() != Some(TRACE_MAGIC) {
+            return Err(TraceError::MissingHeader);
+        }
+        let version = parts.next().unwrap_or_default();
+        if version != TRACE_VERSION.to_string() {
+            return Err(TraceError::UnsupportedVersion(version.to_string()));
+        }
+        let mcc_version = parts.next().unwrap_or_default();
+        let is_winstore = parts.next() == Some("1");
+
+        let mut settings = Vec::new();
+        if let Some((_, line)) = lines.next_if(|(_, l)| l.starts_with("settings")) {
+            for pair in line.split_whitespace().skip(1) {
+                if let Some((name, value)) = pair.split_once('=') {
+                    settings.push((name, value));
+                }
+            }
+        }
+
+        Ok(Self {
+            mcc_version,
+            is_winstore,
+            settings,
+            lines,
+            tick: 0,
+        })
+    }
+
+    // Advances one tick. The returned applier must be run over every watcher in the state.
+    // Returns None once the trace is exhausted.
+    pub fn next_tick(&mut self) -> Result<Option<(u64, TickApplier<'a>)>, TraceError> {
+        while self.lines.next_if(|(_, l)| l.trim().is_empty()).is_some() {}
+
+        let Some(&(index, line)) = self.lines.peek() else { return Ok(None) };
+        let mut parts = line.split_whitespace();
+        let line_tick: u64 = parts.next().and_then(|t| t.parse().ok()).ok_or(TraceError::BadLine(index + 1))?;
+
+        let mut applier = TickApplier {
+            line: index + 1,
+            changes: Vec::new(),
+            error: None,
+        };
+        if line_tick <= self.tick {
+            self.lines.next();
+            for pair in parts {
+                let (name, value) = pair.split_once('=').ok_or(TraceError::BadLine(index + 1))?;
+                applier.changes.push((name, value));
+            }
+        }
+
+        let tick = self.tick;
+        self.tick += 1;
+        Ok(Some((tick, applier)))
+    }
+}
+
+// Feeds one tick of trace values into the watchers. Watchers not mentioned on the tick's
+// line are updated with their current value again, as they would be by a live read.
+pub struct TickApplier<'a> {
+    line: usize,
+    changes: Vec<(&'a str, &'a str)>,
+    error: Option<TraceError>,
+}
+
+impl WatcherVisitor for TickApplier<'_> {
+    fn visit<T: TraceValue>(&mut self, name: &'static str, watcher: &mut Watcher<T>) {
+        let value = match self.changes.iter().find(|(n, _)| *n == name) {
+            Some((_, MISSING)) => None,
+            Some((_, value)) => match T::decode(value) {
+                Some(value) => Some(value),
+                None => {
+                    self.error = Some(TraceError::BadValue(self.line, name.to_string()));
+                    None
+                }
+            },
+            None => watcher.pair.as_ref().map(|p| p.current),
+        };
+        watcher.update(value);
+    }
+}
+
+impl TickApplier<'_> {
+    pub fn finish(self) -> Result<(), TraceError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}